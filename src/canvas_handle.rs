@@ -1,74 +1,715 @@
-use eframe::egui::{Color32, Image, Rect, Response as EguiResponse, Stroke, Ui};
-use eframe::emath::{Align2, Pos2};
-use eframe::epaint::{FontId, Rounding};
+use eframe::egui::Vec2 as GuiVec;
+use eframe::egui::{self, Color32, CursorIcon, Modifiers, Rect, Stroke, Ui};
+use eframe::emath::{Align2, Pos2, Rot2};
+use eframe::epaint::{CircleShape, FontId, Mesh, Rounding, Shape};
 use egui_extras::RetainedImage;
 use replace_with::replace_with_or_abort;
 use simple_math::{Rectangle, Vec2};
 
-use crate::Position;
+use crate::painter::Painter;
+use crate::{Margins, Position, Transform};
 
 ///mirrors the gui
+///the one handle every Drawable draws with independent of the Painter behind it
+///(on screen, svg export, offscreen images or recording)
 pub struct CanvasHandle<'p> {
-    ui: &'p mut Ui,
-    response: &'p mut EguiResponse,
+    painter: &'p mut dyn Painter,
     current_cutout: &'p mut Rect,
     gui_space: Rect,
     aspect_ratio: f32,
+    ///the smallest space between the drawn cutout and each border of the canvas
+    margins: Margins,
+    pick_radius_mode: PickRadiusMode,
+    ///applied to every canvas position before it is converted
+    transform: Transform,
+    ///the transforms before the pushed ones
+    transform_stack: Vec<Transform>,
+    ///Some(dark_mode) inside of with_forced_theme
+    forced_dark_mode: Option<bool>,
+    ///round thin strokes to the pixel grid (see set_pixel_snap)
+    pixel_snap: bool,
 }
 
 impl<'p> CanvasHandle<'p> {
     pub(super) fn new(
-        ui: &'p mut Ui,
-        response: &'p mut EguiResponse,
+        painter: &'p mut dyn Painter,
         current_cutout: &'p mut Rect,
         gui_space: Rect,
         aspect_ratio: f32,
+        margins: Margins,
+        pick_radius_mode: PickRadiusMode,
     ) -> CanvasHandle {
         CanvasHandle {
-            ui,
-            response,
+            painter,
             current_cutout,
             gui_space,
             aspect_ratio,
+            margins,
+            pick_radius_mode,
+            transform: Transform::IDENTITY,
+            transform_stack: Vec::new(),
+            forced_dark_mode: None,
+            pixel_snap: false,
         }
     }
 
+    /// canvas positions are transformed until the matching pop_transform
+    /// the transform is applied before the already pushed transforms
+    /// canvas space results (e.g. convert_to_canvas_space) are in the transformed space
+    /// but the scaling (e.g. pixels_per_canvas_unit) ignores the transform
+    pub fn push_transform(&mut self, transform: Transform) {
+        self.transform_stack.push(self.transform);
+        self.transform = transform.then(self.transform);
+    }
+
+    pub fn pop_transform(&mut self) {
+        self.transform = self.transform_stack.pop().unwrap_or(Transform::IDENTITY);
+    }
+
     pub fn convert_to_overlay_space(&self, pos: Position) -> Position {
-        Position::Overlay(pos.to_overlay_space(
+        Position::Overlay(self.apply_transform(pos).to_overlay_space(
             self.gui_space,
             *self.current_cutout,
             self.aspect_ratio,
+            self.margins,
         ))
     }
 
     pub fn convert_to_canvas_space(&self, pos: Position) -> Position {
-        Position::Canvas(pos.to_canvas_space(
+        if let Position::Canvas(_) = pos {
+            return pos;
+        }
+        let pos = pos.to_canvas_space(
             self.gui_space,
             *self.current_cutout,
             self.aspect_ratio,
-        ))
+            self.margins,
+        );
+        match self.transform.inverse() {
+            Some(inverse) => Position::Canvas(inverse.apply(pos)),
+            None => Position::Canvas(pos),
+        }
     }
 
     fn convert_to_gui_space(&self, pos: Position) -> Pos2 {
-        pos.to_gui_space(self.gui_space, *self.current_cutout, self.aspect_ratio)
+        self.apply_transform(pos).to_gui_space(
+            self.gui_space,
+            *self.current_cutout,
+            self.aspect_ratio,
+            self.margins,
+        )
+    }
+
+    /// thin strokes (about 1 physical pixel wide) of line_segment and rect are moved
+    /// to the centers of the pixels so they are not blurred over two pixels
+    /// this trades exact positions (off by up to half a pixel) for sharp lines
+    /// e.g. for axes and grids
+    /// text and filled shapes are never snapped
+    /// default is false
+    pub fn set_pixel_snap(&mut self, pixel_snap: bool) {
+        self.pixel_snap = pixel_snap;
+    }
+
+    /// the point moved to the center of its pixel if the stroke should be snapped
+    fn snap_to_pixel(&self, point: Pos2, stroke: Stroke) -> Pos2 {
+        let pixels_per_point = self
+            .painter
+            .ui()
+            .map_or(1.0, |ui| ui.ctx().pixels_per_point());
+        let stroke_in_pixels = stroke.width * pixels_per_point;
+        if !self.pixel_snap || !(0.5..1.5).contains(&stroke_in_pixels) {
+            return point;
+        }
+        let snap = |value: f32| ((value * pixels_per_point).floor() + 0.5) / pixels_per_point;
+        Pos2::new(snap(point.x), snap(point.y))
+    }
+
+    fn apply_transform(&self, pos: Position) -> Position {
+        match pos {
+            Position::Canvas(pos) => Position::Canvas(self.transform.apply(pos)),
+            pos => pos,
+        }
+    }
+
+    /// how many gui pixels one canvas unit is long in x and y direction
+    pub fn pixels_per_canvas_unit(&self) -> Vec2 {
+        let (_padding, scaling_factor) = Position::calculate_padding_and_scaling_factor(
+            self.gui_space,
+            *self.current_cutout,
+            self.aspect_ratio,
+            self.margins,
+        );
+        scaling_factor
+    }
+
+    /// converts a vector (e.g. a size or a displacement) from canvas units to gui pixels
+    /// only the scaling is applied and not the padding so this is not for points
+    /// the y axis of the gui points down so flip the sign of y for directions
+    pub fn canvas_vec_to_gui(&self, v: Vec2) -> Vec2 {
+        let scaling_factor = self.pixels_per_canvas_unit();
+        Vec2::new(v.x() * scaling_factor.x(), v.y() * scaling_factor.y())
+    }
+
+    /// converts a vector (e.g. a size or a displacement) from gui pixels to canvas units
+    /// the inverse of canvas_vec_to_gui so this is not for points either
+    /// the y axis of the gui points down so flip the sign of y for directions
+    pub fn gui_vec_to_canvas(&self, v: Vec2) -> Vec2 {
+        let scaling_factor = self.pixels_per_canvas_unit();
+        Vec2::new(v.x() / scaling_factor.x(), v.y() / scaling_factor.y())
+    }
+
+    /// the space in gui pixels between the edge of the canvas and the current cutout
+    /// this includes the letterbox so overlays (e.g. legends) can align to the plot edges
+    pub fn padding(&self) -> Vec2 {
+        let (padding, _scaling_factor) = Position::calculate_padding_and_scaling_factor(
+            self.gui_space,
+            *self.current_cutout,
+            self.aspect_ratio,
+            self.margins,
+        );
+        padding
+    }
+
+    /// true if pos is inside of the canvas
+    /// points exactly on the edge are visible
+    pub fn is_visible(&self, pos: Position) -> bool {
+        self.gui_space.contains(self.convert_to_gui_space(pos))
+    }
+
+    /// the distance between a and b in gui pixels
+    /// e.g. to check if a point was clicked
+    pub fn distance_in_gui(&self, a: Position, b: Position) -> f32 {
+        self.convert_to_gui_space(a)
+            .distance(self.convert_to_gui_space(b))
+    }
+
+    /// the radius around the cursor in gui pixels in which things are picked
+    /// a radius in canvas units is converted with the smaller scaling factor
+    pub fn pick_radius_gui(&self) -> f32 {
+        match self.pick_radius_mode {
+            PickRadiusMode::Pixels(radius) => radius,
+            PickRadiusMode::CanvasUnits(radius) => {
+                let scaling_factor = self.pixels_per_canvas_unit();
+                radius * scaling_factor.x().min(scaling_factor.y())
+            }
+        }
+    }
+
+    /// the radius around the cursor in canvas units in which things are picked
+    /// a radius in pixels is converted for each axis
+    pub fn pick_radius_canvas(&self) -> Vec2 {
+        match self.pick_radius_mode {
+            PickRadiusMode::Pixels(radius) => {
+                let scaling_factor = self.pixels_per_canvas_unit();
+                Vec2::new(radius / scaling_factor.x(), radius / scaling_factor.y())
+            }
+            PickRadiusMode::CanvasUnits(radius) => Vec2::new(radius, radius),
+        }
+    }
+
+    /// true if point is inside of rect_canvas (in canvas space)
+    pub fn contains(&self, rect_canvas: Rect, point: Position) -> bool {
+        rect_canvas.contains(self.convert_to_canvas_space(point).get_raw_pos())
     }
 
     pub fn bounding_box(&self) -> Rectangle {
-        let gui_rect = self.ui.painter().clip_rect();
+        let gui_rect = self.painter.clip_rect();
         Rectangle::new(gui_rect.max.into(), gui_rect.min.into())
     }
 
     pub fn line_segment(&mut self, points: (Position, Position), stroke: impl Into<Stroke>) {
+        let stroke = stroke.into();
+        let points = [
+            self.snap_to_pixel(self.convert_to_gui_space(points.0), stroke),
+            self.snap_to_pixel(self.convert_to_gui_space(points.1), stroke),
+        ];
+        self.painter.line_segment(points, stroke);
+    }
+
+    /// draws an arrow from origin to tip
+    /// head_length is in gui pixels so the head has the same size on every zoom level
+    /// if origin and tip are at the same position no head is drawn
+    pub fn arrow(
+        &mut self,
+        origin: Position,
+        tip: Position,
+        stroke: impl Into<Stroke>,
+        head_length: f32,
+    ) {
+        let stroke = stroke.into();
+        let origin = self.convert_to_gui_space(origin);
+        let tip = self.convert_to_gui_space(tip);
+        self.painter.line_segment([origin, tip], stroke);
+
+        let vec = tip - origin;
+        if vec.length_sq() > 0.0 {
+            let rot = Rot2::from_angle(std::f32::consts::TAU / 10.0);
+            let dir = vec.normalized();
+            self.painter
+                .line_segment([tip, tip - head_length * (rot * dir)], stroke);
+            self.painter
+                .line_segment([tip, tip - head_length * (rot.inverse() * dir)], stroke);
+        }
+    }
+
+    /// draws a dashed line
+    /// dash_length and gap_length are in gui pixels
+    /// so the pattern looks the same on every zoom level
+    pub fn line_segment_dashed(
+        &mut self,
+        points: (Position, Position),
+        stroke: impl Into<Stroke>,
+        dash_length: f32,
+        gap_length: f32,
+    ) {
+        if dash_length <= 0.0 {
+            return;
+        }
+        let stroke = stroke.into();
+        let gap_length = gap_length.max(0.0);
+        let [start, end] = [
+            self.convert_to_gui_space(points.0),
+            self.convert_to_gui_space(points.1),
+        ];
+        //only the visible part is dashed so a zoomed in line does not produce millions of dashes
+        let clip_rect = self.painter.clip_rect().expand(stroke.width);
+        let (t_start, t_end) = match clip_segment([start, end], clip_rect) {
+            Some(visible) => visible,
+            None => return,
+        };
+        let length = start.distance(end);
+        let points = if length > 0.0 && length.is_finite() {
+            let direction = (end - start) / length;
+            //start at a whole period so the pattern does not move with the clip rect
+            let period = dash_length + gap_length;
+            let skipped = (t_start * length / period).floor() * period;
+            [
+                start + direction * skipped,
+                start + direction * (t_end * length),
+            ]
+        } else {
+            [start, end]
+        };
+        let dashes = Shape::dashed_line(&points, stroke, dash_length, gap_length);
+        for dash in dashes {
+            if let Shape::LineSegment { points, stroke } = dash {
+                self.painter.line_segment(points, stroke);
+            }
+        }
+    }
+
+    /// draws the border of the rect between the corners a and b with moving dashes
+    /// speed is in gui pixels per second and the direction is clockwise on screen
+    /// requests a repaint so the dashes keep moving
+    pub fn marching_ants(
+        &mut self,
+        a: Position,
+        b: Position,
+        stroke: impl Into<Stroke>,
+        speed: f32,
+    ) {
+        const DASH_LENGTH: f32 = 4.0;
+        const GAP_LENGTH: f32 = 4.0;
+        const PERIOD: f32 = DASH_LENGTH + GAP_LENGTH;
+
+        let stroke = stroke.into();
+        let rect = Rect::from_two_pos(self.convert_to_gui_space(a), self.convert_to_gui_space(b));
+        //only the visible part is dashed so a zoomed in rect does not produce millions of dashes
+        let visible = rect.intersect(self.painter.clip_rect().expand(stroke.width));
+        if visible.is_negative() {
+            return;
+        }
+        let corners = [
+            visible.left_top(),
+            visible.right_top(),
+            visible.right_bottom(),
+            visible.left_bottom(),
+            visible.left_top(),
+        ];
+        //the edges of visible that are cut by the clip rect are not part of the border
+        let on_border = [
+            visible.top() == rect.top(),
+            visible.right() == rect.right(),
+            visible.bottom() == rect.bottom(),
+            visible.left() == rect.left(),
+        ];
+        let perimeter = 2.0 * (visible.width() + visible.height());
+        if !(perimeter.is_finite() && perimeter > 0.0) {
+            return;
+        }
+
+        let phase = (self.time() as f32 * speed).rem_euclid(PERIOD);
+        let mut dash_start = phase - PERIOD;
+        while dash_start < perimeter {
+            let dash_end = dash_start + DASH_LENGTH;
+            //a dash around a corner is split into one segment per edge
+            let mut edge_start = 0.0;
+            for (edge, on_border) in corners.windows(2).zip(on_border) {
+                let edge_length = edge[0].distance(edge[1]);
+                let from = dash_start.max(edge_start);
+                let to = dash_end.min(edge_start + edge_length);
+                if on_border && from < to && edge_length > 0.0 {
+                    let direction = (edge[1] - edge[0]) / edge_length;
+                    self.painter.line_segment(
+                        [
+                            edge[0] + direction * (from - edge_start),
+                            edge[0] + direction * (to - edge_start),
+                        ],
+                        stroke,
+                    );
+                }
+                edge_start += edge_length;
+            }
+            dash_start += PERIOD;
+        }
+
+        self.request_repaint();
+    }
+
+    /// draws a dotted line
+    /// spacing is the distance between the dots in gui pixels
+    /// so the pattern looks the same on every zoom level
+    pub fn line_segment_dotted(
+        &mut self,
+        points: (Position, Position),
+        color: impl Into<Color32>,
+        spacing: f32,
+        radius: f32,
+    ) {
+        if spacing <= 0.0 {
+            return;
+        }
         let points = [
             self.convert_to_gui_space(points.0),
             self.convert_to_gui_space(points.1),
         ];
-        self.ui.painter().line_segment(points, stroke);
+        let dots = Shape::dotted_line(&points, color, spacing, radius);
+        for dot in dots {
+            if let Shape::Circle(CircleShape {
+                center,
+                radius,
+                fill,
+                ..
+            }) = dot
+            {
+                self.painter.circle_filled(center, radius, fill);
+            }
+        }
+    }
+
+    /// draws an open line through the points
+    pub fn polyline(
+        &mut self,
+        points: impl IntoIterator<Item = Position>,
+        stroke: impl Into<Stroke>,
+    ) {
+        self.polyline_styled(points, stroke, LineCap::default(), LineJoin::default());
+    }
+
+    /// draws a cubic bezier curve from the first to the last control point
+    /// the curve is sampled in gui space about every 4 gui pixels
+    /// so it stays smooth on every zoom level
+    pub fn cubic_bezier(&mut self, control_points: [Position; 4], stroke: impl Into<Stroke>) {
+        let control_points = control_points.map(|point| self.convert_to_gui_space(point));
+        let mut points = vec![control_points[0]];
+        sample_cubic_bezier(control_points, &mut points);
+        self.polyline(points.into_iter().map(Position::Gui), stroke);
+    }
+
+    /// draws a smooth curve through the points (a catmull-rom spline)
+    /// the spline is fitted and sampled in gui space like cubic_bezier
+    /// so the curve looks the same for every aspect ratio
+    /// fewer than 2 points draw nothing
+    pub fn smooth_line(
+        &mut self,
+        points: impl IntoIterator<Item = Position>,
+        stroke: impl Into<Stroke>,
+    ) {
+        let control_points: Vec<Pos2> = points
+            .into_iter()
+            .map(|point| self.convert_to_gui_space(point))
+            .collect();
+        if control_points.len() < 2 {
+            return;
+        }
+
+        let last = control_points.len() - 1;
+        let mut points = vec![control_points[0]];
+        for index in 0..last {
+            let before = control_points[index.saturating_sub(1)];
+            let start = control_points[index];
+            let end = control_points[index + 1];
+            let after = control_points[(index + 2).min(last)];
+            //the bezier control points of the catmull-rom segment
+            let bezier = [
+                start,
+                start + (end - before) / 6.0,
+                end - (after - start) / 6.0,
+                end,
+            ];
+            sample_cubic_bezier(bezier, &mut points);
+        }
+        self.polyline(points.into_iter().map(Position::Gui), stroke);
+    }
+
+    /// draws an open line through the points with the given caps and joins
+    /// the defaults (Butt and Miter) are the same as polyline
+    /// round caps and joins are circles painted over the line
+    /// so use an opaque color with them because a translucent one is blended twice where they overlap
+    pub fn polyline_styled(
+        &mut self,
+        points: impl IntoIterator<Item = Position>,
+        stroke: impl Into<Stroke>,
+        cap: LineCap,
+        join: LineJoin,
+    ) {
+        let stroke = stroke.into();
+        let mut points: Vec<Pos2> = points
+            .into_iter()
+            .map(|point| self.convert_to_gui_space(point))
+            .collect();
+        if points.len() < 2 {
+            return;
+        }
+
+        let half_width = stroke.width / 2.0;
+        if cap == LineCap::Square {
+            //extend the first and the last segment by half the stroke width
+            let last = points.len() - 1;
+            let start_direction = (points[0] - points[1]).normalized();
+            let end_direction = (points[last] - points[last - 1]).normalized();
+            points[0] += start_direction * half_width;
+            points[last] += end_direction * half_width;
+        }
+
+        if join == LineJoin::Round {
+            for point in &points[1..points.len() - 1] {
+                self.painter.circle_filled(*point, half_width, stroke.color);
+            }
+        }
+        if cap == LineCap::Round {
+            for point in [points[0], points[points.len() - 1]] {
+                self.painter.circle_filled(point, half_width, stroke.color);
+            }
+        }
+
+        self.painter.polyline(points, stroke);
+    }
+
+    /// draws a stairstep line through the points (e.g. for signals and states)
+    /// the steps are inserted in canvas space so they stay axis aligned
+    /// the x values are expected to be monotonic
+    /// unsorted x values do not panic but the steps go backwards
+    pub fn polyline_step(
+        &mut self,
+        points: impl IntoIterator<Item = Position>,
+        stroke: impl Into<Stroke>,
+        step: StepMode,
+    ) {
+        let points: Vec<Pos2> = points
+            .into_iter()
+            .map(|point| self.convert_to_canvas_space(point).get_raw_pos())
+            .collect();
+
+        let mut stepped = Vec::with_capacity(points.len() * 3);
+        for (index, point) in points.iter().enumerate() {
+            if let Some(previous) = index.checked_sub(1).map(|index| points[index]) {
+                match step {
+                    StepMode::Before => stepped.push(Pos2::new(previous.x, point.y)),
+                    StepMode::After => stepped.push(Pos2::new(point.x, previous.y)),
+                    StepMode::Center => {
+                        let center_x = (previous.x + point.x) / 2.0;
+                        stepped.push(Pos2::new(center_x, previous.y));
+                        stepped.push(Pos2::new(center_x, point.y));
+                    }
+                }
+            }
+            stepped.push(*point);
+        }
+        self.polyline(stepped.into_iter().map(Position::Canvas), stroke);
+    }
+
+    /// draws an open line through the points that fades in from transparent at the first point
+    /// to color at the last point (e.g. for the trail of a trajectory)
+    /// width is in gui pixels
+    pub fn fading_polyline(&mut self, points: &[Position], color: Color32, width: f32) {
+        let points: Vec<Pos2> = points
+            .iter()
+            .map(|point| self.convert_to_gui_space(*point))
+            .collect();
+        if points.len() < 2 {
+            return;
+        }
+
+        //a ribbon with two vertices per point
+        let half_width = width / 2.0;
+        let last = points.len() - 1;
+        let mut mesh = Mesh::default();
+        let mut normal = egui::Vec2::ZERO;
+        for (index, point) in points.iter().enumerate() {
+            let direction = points[(index + 1).min(last)] - points[index.saturating_sub(1)];
+            //keep the last normal if the points are on top of each other
+            if direction.length() > 0.0 {
+                normal = direction.normalized().rot90();
+            }
+            let color = color.linear_multiply(index as f32 / last as f32);
+            mesh.colored_vertex(*point + normal * half_width, color);
+            mesh.colored_vertex(*point - normal * half_width, color);
+        }
+        for index in 0..last as u32 {
+            let vertex = 2 * index;
+            mesh.add_triangle(vertex, vertex + 1, vertex + 2);
+            mesh.add_triangle(vertex + 1, vertex + 3, vertex + 2);
+        }
+        self.painter.mesh(mesh);
+    }
+
+    /// draws triangles with a color per vertex (e.g. for heatmaps)
+    /// every three indices are one triangle
+    /// nothing is drawn if there are no triangles or an index is out of bounds
+    pub fn mesh(&mut self, vertices: &[(Position, Color32)], indices: &[u32]) {
+        let valid = !indices.is_empty()
+            && indices.chunks_exact(3).remainder().is_empty()
+            && indices
+                .iter()
+                .all(|index| (*index as usize) < vertices.len());
+        if !valid {
+            return;
+        }
+
+        let mut mesh = Mesh::default();
+        for (position, color) in vertices {
+            mesh.colored_vertex(self.convert_to_gui_space(*position), *color);
+        }
+        mesh.indices.extend_from_slice(indices);
+        self.painter.mesh(mesh);
     }
 
     pub fn circle_filled(&mut self, center: Position, radius: f32, fill_color: impl Into<Color32>) {
         let center = self.convert_to_gui_space(center);
-        self.ui.painter().circle_filled(center, radius, fill_color);
+        self.painter
+            .circle_filled(center, radius, fill_color.into());
+    }
+
+    /// draws a filled circle with the radius in gui pixels at every center
+    /// e.g. for scatter plots
+    /// the circles are batched into a single mesh (see markers)
+    pub fn points(
+        &mut self,
+        centers: impl IntoIterator<Item = Position>,
+        radius: f32,
+        fill_color: impl Into<Color32>,
+    ) {
+        self.markers(centers, MarkerShape::Circle, radius, fill_color);
+    }
+
+    /// draws the marker with the radius in gui pixels at every center
+    /// markers outside of the clip rect are skipped
+    /// circles and squares are batched into a single mesh so they are fast for many points
+    /// the batched circles are polygons without the anti aliasing of circle_filled
+    pub fn markers(
+        &mut self,
+        centers: impl IntoIterator<Item = Position>,
+        shape: MarkerShape,
+        radius: f32,
+        color: impl Into<Color32>,
+    ) {
+        let color = color.into();
+        let visible_rect = self.painter.clip_rect().expand(radius);
+        let centers = centers
+            .into_iter()
+            .map(|center| self.convert_to_gui_space(center))
+            .filter(|center| visible_rect.contains(*center))
+            .collect::<Vec<_>>();
+
+        let stroke = Stroke::new((radius / 2.0).max(1.0), color);
+        let diagonal = radius * std::f32::consts::FRAC_1_SQRT_2;
+        match shape {
+            MarkerShape::Circle => {
+                //enough corners that the polygon looks round at the radius
+                let num_corners = ((radius * 2.0).ceil() as u32).clamp(8, 64);
+                let corners: Vec<GuiVec> = (0..num_corners)
+                    .map(|index| {
+                        let angle = index as f32 / num_corners as f32 * std::f32::consts::TAU;
+                        radius * GuiVec::angled(angle)
+                    })
+                    .collect();
+
+                //one triangle fan per circle
+                let mut mesh = Mesh::default();
+                for center in centers {
+                    let first = mesh.vertices.len() as u32;
+                    mesh.colored_vertex(center, color);
+                    for corner in &corners {
+                        mesh.colored_vertex(center + *corner, color);
+                    }
+                    for index in 0..num_corners {
+                        let next = (index + 1) % num_corners;
+                        mesh.add_triangle(first, first + 1 + index, first + 1 + next);
+                    }
+                }
+                if !mesh.is_empty() {
+                    self.painter.mesh(mesh);
+                }
+            }
+            MarkerShape::Square => {
+                let mut mesh = Mesh::default();
+                for center in centers {
+                    let rect = Rect::from_center_size(center, GuiVec::splat(2.0 * radius));
+                    mesh.add_colored_rect(rect, color);
+                }
+                if !mesh.is_empty() {
+                    self.painter.mesh(mesh);
+                }
+            }
+            MarkerShape::Cross => {
+                for center in centers {
+                    let offset = egui::vec2(diagonal, diagonal);
+                    let mirrored_offset = egui::vec2(diagonal, -diagonal);
+                    self.painter
+                        .line_segment([center - offset, center + offset], stroke);
+                    self.painter
+                        .line_segment([center - mirrored_offset, center + mirrored_offset], stroke);
+                }
+            }
+            MarkerShape::Plus => {
+                for center in centers {
+                    let horizontal = egui::vec2(radius, 0.0);
+                    let vertical = egui::vec2(0.0, radius);
+                    self.painter
+                        .line_segment([center - horizontal, center + horizontal], stroke);
+                    self.painter
+                        .line_segment([center - vertical, center + vertical], stroke);
+                }
+            }
+        }
+    }
+
+    /// draws an ellipse with the radii in canvas space
+    /// the rotation (in radians) is applied in canvas space before the x/y scaling
+    /// so the ellipse covers the same canvas region at every zoom
+    pub fn ellipse(
+        &mut self,
+        center: Position,
+        radii_canvas: Vec2,
+        rotation: f32,
+        fill_color: impl Into<Color32>,
+        stroke: impl Into<Stroke>,
+    ) {
+        //the number of points the ellipse is approximated with
+        const NUM_POINTS: usize = 64;
+
+        let center = self.convert_to_canvas_space(center).get_raw_pos();
+        let rotation = Rot2::from_angle(rotation);
+        let points = (0..NUM_POINTS).map(|index| {
+            let angle = index as f32 / NUM_POINTS as f32 * std::f32::consts::TAU;
+            let offset = egui::vec2(
+                radii_canvas.x() * angle.cos(),
+                radii_canvas.y() * angle.sin(),
+            );
+            Position::Canvas(center + rotation * offset)
+        });
+        self.polygon(points, fill_color, stroke);
     }
 
     pub fn rect(
@@ -79,11 +720,52 @@ impl<'p> CanvasHandle<'p> {
         fill_color: impl Into<Color32>,
         stroke: impl Into<Stroke>,
     ) {
-        let corner_a = self.convert_to_gui_space(corner_a);
-        let corner_b = self.convert_to_gui_space(corner_b);
+        let fill_color = fill_color.into();
+        let stroke = stroke.into();
+        let mut corner_a = self.convert_to_gui_space(corner_a);
+        let mut corner_b = self.convert_to_gui_space(corner_b);
+        if fill_color == Color32::TRANSPARENT {
+            corner_a = self.snap_to_pixel(corner_a, stroke);
+            corner_b = self.snap_to_pixel(corner_b, stroke);
+        }
         let rect = Rect::from_two_pos(corner_a, corner_b);
 
-        self.ui.painter().rect(rect, rounding, fill_color, stroke);
+        self.painter.rect(rect, rounding.into(), fill_color, stroke);
+    }
+
+    /// like rect with the corners of the Rectangle in canvas space
+    /// e.g. for the result of get_draw_region_in_canvas_space
+    pub fn rect_canvas(
+        &mut self,
+        rect: Rectangle,
+        rounding: impl Into<Rounding>,
+        fill_color: impl Into<Color32>,
+        stroke: impl Into<Stroke>,
+    ) {
+        let corner_a = Position::Canvas(rect.min().into());
+        let corner_b = Position::Canvas(rect.max().into());
+        self.rect(corner_a, corner_b, rounding, fill_color, stroke);
+    }
+
+    /// draws a closed polygon through the points
+    /// fewer than 3 points draw nothing
+    /// the polygon is drawn as a convex polygon
+    /// so concave or self-intersecting polygons may render incorrectly
+    pub fn polygon(
+        &mut self,
+        points: impl IntoIterator<Item = Position>,
+        fill_color: impl Into<Color32>,
+        stroke: impl Into<Stroke>,
+    ) {
+        let points: Vec<Pos2> = points
+            .into_iter()
+            .map(|point| self.convert_to_gui_space(point))
+            .collect();
+        if points.len() < 3 {
+            return;
+        }
+        self.painter
+            .polygon(points, fill_color.into(), stroke.into());
     }
 
     pub fn text(
@@ -95,68 +777,402 @@ impl<'p> CanvasHandle<'p> {
         text_color: Color32,
     ) {
         let pos = self.convert_to_gui_space(pos);
-        self.ui
-            .painter()
-            .text(pos, anchor, text, font_id, text_color);
+        self.painter
+            .text(pos, anchor, text.to_string(), font_id, text_color);
+    }
+
+    /// draws the text and returns its size
+    /// cheaper than calling text_size and text because the text is only layed out once
+    pub fn text_galley(
+        &mut self,
+        pos: Position,
+        anchor: Align2,
+        text: impl ToString,
+        font_id: FontId,
+        text_color: Color32,
+    ) -> Vec2 {
+        let pos = self.convert_to_gui_space(pos);
+        self.painter
+            .text_galley(pos, anchor, text.to_string(), font_id, text_color)
+            .into()
+    }
+
+    /// draws the text on a rounded box so it stays readable over busy plots
+    /// padding is the space between the text and the border of the box in gui pixels
+    /// the anchor refers to the box and not to the text
+    #[allow(clippy::too_many_arguments)]
+    pub fn text_boxed(
+        &mut self,
+        pos: Position,
+        anchor: Align2,
+        text: impl ToString,
+        font_id: FontId,
+        text_color: Color32,
+        bg_color: Color32,
+        padding: f32,
+    ) {
+        let text = text.to_string();
+        let text_size: GuiVec = self.painter.text_size(text.clone(), font_id.clone());
+        let pos = self.convert_to_gui_space(pos);
+        let box_rect = anchor.anchor_rect(Rect::from_min_size(
+            pos,
+            text_size + GuiVec::splat(2.0 * padding),
+        ));
+
+        self.painter
+            .rect(box_rect, Rounding::same(2.0), bg_color, Stroke::none());
+        self.painter.text(
+            box_rect.center(),
+            Align2::CENTER_CENTER,
+            text,
+            font_id,
+            text_color,
+        );
     }
 
     pub fn text_size(&self, text: impl ToString, font_id: FontId) -> Vec2 {
-        //color is just a dummy value
-        let gally = self
-            .ui
-            .painter()
-            .layout_no_wrap(text.to_string(), font_id, Color32::BLACK);
-        gally.size().into()
+        self.painter.text_size(text.to_string(), font_id).into()
+    }
+
+    /// draws the text at a fixed place of the canvas (e.g. Align2::RIGHT_TOP for the top right corner)
+    /// margin is the distance to the border of the canvas in gui pixels
+    /// independent of the cutout so it is meant for HUD elements
+    pub fn screen_text(
+        &mut self,
+        anchor: Align2,
+        margin: f32,
+        text: impl ToString,
+        font_id: FontId,
+        text_color: Color32,
+    ) {
+        let pos = anchor.pos_in_rect(&self.gui_space.shrink(margin));
+        self.painter
+            .text(pos, anchor, text.to_string(), font_id, text_color);
+    }
+
+    /// draws a rect of the size (in gui pixels) at a fixed place of the canvas
+    /// like screen_text with the rect aligned to the anchor
+    pub fn screen_rect(
+        &mut self,
+        anchor: Align2,
+        margin: f32,
+        size: Vec2,
+        rounding: impl Into<Rounding>,
+        fill_color: impl Into<Color32>,
+        stroke: impl Into<Stroke>,
+    ) {
+        let rect = anchor.align_size_within_rect(size.into(), self.gui_space.shrink(margin));
+        self.painter
+            .rect(rect, rounding.into(), fill_color.into(), stroke.into());
+    }
+
+    /// draws the labels next to their positions without overlapping each other
+    /// earlier labels have priority, a label is tried on every side of its position
+    /// and dropped if it overlaps on every side or its position is not visible
+    /// returns for every label if it was drawn
+    pub fn labels_no_overlap(
+        &mut self,
+        labels: &[(Position, String)],
+        font_id: FontId,
+        text_color: Color32,
+    ) -> Vec<bool> {
+        //the space in gui pixels between the position and the label
+        const OFFSET: f32 = 3.0;
+        //above right first then clockwise
+        let candidates = [
+            (Align2::LEFT_BOTTOM, egui::vec2(OFFSET, -OFFSET)),
+            (Align2::LEFT_TOP, egui::vec2(OFFSET, OFFSET)),
+            (Align2::RIGHT_TOP, egui::vec2(-OFFSET, OFFSET)),
+            (Align2::RIGHT_BOTTOM, egui::vec2(-OFFSET, -OFFSET)),
+        ];
+
+        let mut occupied: Vec<Rect> = Vec::with_capacity(labels.len());
+        labels
+            .iter()
+            .map(|(pos, text)| {
+                if !self.is_visible(*pos) {
+                    return false;
+                }
+                let pos = self.convert_to_gui_space(*pos);
+                let size = self.painter.text_size(text.clone(), font_id.clone());
+
+                let free_candidate = candidates.iter().find_map(|(anchor, offset)| {
+                    let rect = anchor.anchor_rect(Rect::from_min_size(pos + *offset, size));
+                    let free = occupied.iter().all(|other| !other.intersects(rect));
+                    free.then_some((*anchor, rect))
+                });
+
+                match free_candidate {
+                    Some((anchor, rect)) => {
+                        self.painter.text(
+                            anchor.pos_in_rect(&rect),
+                            anchor,
+                            text.clone(),
+                            font_id.clone(),
+                            text_color,
+                        );
+                        occupied.push(rect);
+                        true
+                    }
+                    None => false,
+                }
+            })
+            .collect()
     }
 
     pub fn request_repaint(&self) {
-        self.ui.ctx().request_repaint();
+        if let Some(ui) = self.painter.ui() {
+            ui.ctx().request_repaint();
+        }
+    }
+
+    ///the scroll of this frame in gui pixels
+    ///the canvas zooms with the vertical scroll while it is hovered
+    ///so set_zoom_sensitivity(0.0) before using it for something else
+    ///zero if the canvas is not painted into egui (e.g. for exporting)
+    pub fn scroll_delta(&self) -> Vec2 {
+        self.painter.ui().map_or(Vec2::new(0.0, 0.0), |ui| {
+            ui.ctx().input().scroll_delta.into()
+        })
+    }
+
+    ///the zoom factor of this frame (e.g. from pinching or ctrl + scroll)
+    ///1.0 for no zoom
+    ///the canvas only zooms with it if its scroll behavior is not ScrollBehavior::Zoom
+    ///1.0 if the canvas is not painted into egui (e.g. for exporting)
+    pub fn zoom_delta(&self) -> f32 {
+        self.painter
+            .ui()
+            .map_or(1.0, |ui| ui.ctx().input().zoom_delta())
+    }
+
+    ///true while the primary mouse button is held down anywhere
+    ///false if the canvas is not painted into egui (e.g. for exporting)
+    pub fn pointer_primary_down(&self) -> bool {
+        self.painter
+            .ui()
+            .is_some_and(|ui| ui.ctx().input().pointer.primary_down())
+    }
+
+    ///the modifier keys held down in this frame
+    ///none if the canvas is not painted into egui (e.g. for exporting)
+    pub fn modifiers(&self) -> Modifiers {
+        self.painter
+            .ui()
+            .map_or(Modifiers::NONE, |ui| ui.ctx().input().modifiers)
     }
 
     ///returns the time in seconds relatvie to something
+    ///0.0 if the canvas is not painted into egui (e.g. for exporting)
     pub fn time(&self) -> f64 {
-        self.ui.ctx().input().time
+        self.painter.ui().map_or(0.0, |ui| ui.ctx().input().time)
     }
 
     pub fn cursor_pos(&self) -> Option<Position> {
-        self.ui.ctx().input().pointer.hover_pos().map(Position::Gui)
+        self.painter
+            .ui()
+            .and_then(|ui| ui.ctx().input().pointer.hover_pos())
+            .map(Position::Gui)
+    }
+
+    /// the cursor in (gui, overlay, canvas) space
+    /// None if the cursor is not over the canvas
+    /// the padding and scaling are only calculated once for all conversions
+    pub fn cursor_positions(&self) -> Option<(Position, Position, Position)> {
+        let gui_pos = self.cursor_pos()?.get_raw_pos();
+        if !self.gui_space.contains(gui_pos) {
+            return None;
+        }
+
+        let (padding, scaling_factor) = Position::calculate_padding_and_scaling_factor(
+            self.gui_space,
+            *self.current_cutout,
+            self.aspect_ratio,
+            self.margins,
+        );
+        let overlay_pos = Position::flip_y(gui_pos, self.gui_space);
+        //the same conversion as convert_to_canvas_space so the round trip gui -> canvas -> gui is exact
+        let canvas_pos = Position::overlay_to_canvas(
+            overlay_pos,
+            self.gui_space,
+            *self.current_cutout,
+            (padding, scaling_factor),
+        );
+        let canvas_pos = match self.transform.inverse() {
+            Some(inverse) => inverse.apply(canvas_pos),
+            None => canvas_pos,
+        };
+
+        Some((
+            Position::Gui(gui_pos),
+            Position::Overlay(overlay_pos),
+            Position::Canvas(canvas_pos),
+        ))
+    }
+
+    ///sets the cursor icon for this frame
+    ///the last call in a frame wins and the icon is reset to the default every frame
+    pub fn set_cursor(&self, icon: CursorIcon) {
+        if let Some(ui) = self.painter.ui() {
+            ui.output().cursor_icon = icon;
+        }
     }
 
     pub fn set_aspect_ratio(&mut self, aspect_ratio: f32) {
         self.aspect_ratio = aspect_ratio
     }
 
+    ///images are only painted into egui and skipped otherwise (e.g. for exporting)
     pub fn image(&mut self, image: &RetainedImage, corner_a: Position, corner_b: Position) {
-        let a = self.convert_to_gui_space(corner_a);
-        let b = self.convert_to_gui_space(corner_b);
+        let uv = Rect::from_min_max(Pos2::ZERO, Pos2::new(1.0, 1.0));
+        self.image_ex(image, corner_a, corner_b, 0.0, Color32::WHITE, uv);
+    }
 
-        let [x, y] = image.size();
-        let image = Image::new(image.texture_id(self.ui.ctx()), (x as f32, y as f32));
+    /// like image but rotated, tinted and only showing the uv region of the image
+    /// rotation is in radians and applied in gui space about the center of the rect
+    /// the rect is scaled like the canvas before it is rotated
+    /// so a canvas that scales x and y differently stretches the image along the gui axes
+    /// uv is the region of the image in 0.0..=1.0 (the unit rect for the whole image)
+    /// the tint is multiplied with the image (e.g. Color32::from_white_alpha(128) to fade it)
+    pub fn image_ex(
+        &mut self,
+        image: &RetainedImage,
+        corner_a: Position,
+        corner_b: Position,
+        rotation: f32,
+        tint: Color32,
+        uv: Rect,
+    ) {
+        let rect = Rect::from_two_pos(
+            self.convert_to_gui_space(corner_a),
+            self.convert_to_gui_space(corner_b),
+        );
 
-        image.paint_at(self.ui, Rect::from_two_pos(a, b));
+        let texture_id = match self.painter.ui() {
+            Some(ui) => image.texture_id(ui.ctx()),
+            None => return,
+        };
+        let mut mesh = Mesh::with_texture(texture_id);
+        mesh.add_rect_with_uv(rect, uv, tint);
+        if rotation != 0.0 {
+            mesh.rotate(Rot2::from_angle(rotation), rect.center());
+        }
+        //painted as a shape so it is part of the cached data (see set_cache_static)
+        self.painter.shape(Shape::mesh(mesh));
     }
 
     pub fn on_hover_ui_at_pointer(&mut self, add_contents: impl FnOnce(&mut Ui)) {
-        replace_with_or_abort(self.response, |respones| {
-            respones.on_hover_ui_at_pointer(add_contents)
-        });
+        if let Some(response) = self.painter.response_mut() {
+            replace_with_or_abort(response, |respones| {
+                respones.on_hover_ui_at_pointer(add_contents)
+            });
+        }
     }
 
     /// returs the Rectangle in the canvas space that is currently visual
     /// in general, this is not equal to the current cutout
     /// but bigger in one dimension
+    /// Drawables can skip everything outside of it (e.g. for large datasets)
+    /// with a pushed transform it is the bounding box of the region in the transformed space
     pub fn get_draw_region_in_canvas_space(&self) -> Rectangle {
-        let corner_a = Position::Gui(self.gui_space.min);
-        let corner_b = Position::Gui(self.gui_space.max);
+        let to_canvas_space = |corner| {
+            Position::Gui(corner).to_canvas_space(
+                self.gui_space,
+                *self.current_cutout,
+                self.aspect_ratio,
+                self.margins,
+            )
+        };
+        let region = Rect::from_two_pos(
+            to_canvas_space(self.gui_space.min),
+            to_canvas_space(self.gui_space.max),
+        );
+        //the whole region is transformed because a rotated region is not spanned by two corners
+        let region = match self.transform.inverse() {
+            Some(inverse) => inverse.apply_to_rect(region),
+            None => region,
+        };
+
+        Rectangle::new(region.min.into(), region.max.into())
+    }
 
-        let corner_a = self.convert_to_canvas_space(corner_a).get_raw_pos().into();
-        let corner_b = self.convert_to_canvas_space(corner_b).get_raw_pos().into();
+    /// the multiples of spacing (in canvas space) that are currently visible
+    /// row by row from the bottom left
+    /// at most 100_000 points are yielded so zooming out does not iterate forever
+    pub fn visible_grid_cells(&self, spacing: Vec2) -> impl Iterator<Item = Pos2> {
+        const MAX_GRID_CELLS: usize = 100_000;
 
-        Rectangle::new(corner_a, corner_b)
+        let region = self.get_draw_region_in_canvas_space();
+        let index_range = |a: f32, b: f32, spacing: f32| {
+            (a.min(b) / spacing).ceil() as i64..=(a.max(b) / spacing).floor() as i64
+        };
+        let x_range = index_range(region.left(), region.right(), spacing.x());
+        let y_range = index_range(region.bottom(), region.top(), spacing.y());
+        let ranges = (spacing.x() > 0.0 && spacing.y() > 0.0).then_some((x_range, y_range));
+
+        ranges
+            .into_iter()
+            .flat_map(move |(x_range, y_range)| {
+                y_range.flat_map(move |y| {
+                    x_range
+                        .clone()
+                        .map(move |x| Pos2::new(x as f32 * spacing.x(), y as f32 * spacing.y()))
+                })
+            })
+            .take(MAX_GRID_CELLS)
+    }
+
+    /// the Rectangle in the canvas space that is currently visible
+    /// same as get_draw_region_in_canvas_space
+    pub fn visible_canvas_rect(&self) -> Rectangle {
+        self.get_draw_region_in_canvas_space()
     }
 
+    ///false if the painter does not know the theme (e.g. for exporting)
     pub fn dark_mode(&self) -> bool {
-        self.ui.style().visuals.dark_mode
+        self.forced_dark_mode
+            .unwrap_or_else(|| self.painter.dark_mode())
+    }
+
+    ///the color for lines and text that contrasts with the background of the theme
+    pub fn foreground_color(&self) -> Color32 {
+        if self.dark_mode() {
+            Color32::WHITE
+        } else {
+            Color32::BLACK
+        }
+    }
+
+    ///inside of the closure dark_mode and foreground_color report the forced theme
+    ///e.g. to export with a light theme while the app is in dark mode
+    pub fn with_forced_theme(&mut self, light: bool, f: impl FnOnce(&mut CanvasHandle)) {
+        let old_forced_dark_mode = self.forced_dark_mode.replace(!light);
+        f(self);
+        self.forced_dark_mode = old_forced_dark_mode;
+    }
+
+    /// draws into the gui_rect as if it was its own canvas showing the cutout
+    /// the closure gets a handle that is clipped to the gui_rect
+    /// changes to the cutout inside of the closure are discarded
+    pub fn inset(&mut self, gui_rect: Rect, cutout: Rect, f: impl FnOnce(&mut CanvasHandle)) {
+        let old_clip_rect = self.painter.clip_rect();
+        self.painter
+            .set_clip_rect(old_clip_rect.intersect(gui_rect));
+
+        let mut inset_cutout = cutout;
+        let mut inset_handle = CanvasHandle::new(
+            self.painter,
+            &mut inset_cutout,
+            gui_rect,
+            self.aspect_ratio,
+            self.margins,
+            self.pick_radius_mode,
+        );
+        inset_handle.forced_dark_mode = self.forced_dark_mode;
+        inset_handle.pixel_snap = self.pixel_snap;
+        f(&mut inset_handle);
+
+        self.painter.set_clip_rect(old_clip_rect);
     }
 
     /// translates the current cutout with translation
@@ -165,3 +1181,172 @@ impl<'p> CanvasHandle<'p> {
         *self.current_cutout = self.current_cutout.translate(translation.into());
     }
 }
+
+/// the glyph markers and points draw at every center
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MarkerShape {
+    #[default]
+    Circle,
+    /// an axis aligned square with the radius as half of its side
+    Square,
+    /// an x with the radius as half of its diagonals
+    Cross,
+    /// a +
+    Plus,
+}
+
+///the part of the segment inside of the rect as the range of t in start + t * (end - start)
+///None if the segment misses the rect
+pub(crate) fn clip_segment(points: [Pos2; 2], rect: Rect) -> Option<(f32, f32)> {
+    let [start, end] = points;
+    let delta = end - start;
+    let (mut t_start, mut t_end) = (0.0_f32, 1.0_f32);
+    //the distances to the four borders (negative outside) and how fast t approaches them
+    let borders = [
+        (-delta.x, start.x - rect.min.x),
+        (delta.x, rect.max.x - start.x),
+        (-delta.y, start.y - rect.min.y),
+        (delta.y, rect.max.y - start.y),
+    ];
+    for (direction, distance) in borders {
+        if direction == 0.0 {
+            if distance < 0.0 {
+                //parallel to the border and outside of it
+                return None;
+            }
+        } else {
+            let t = distance / direction;
+            if direction < 0.0 {
+                t_start = t_start.max(t);
+            } else {
+                t_end = t_end.min(t);
+            }
+        }
+    }
+    (t_start <= t_end).then_some((t_start, t_end))
+}
+
+///appends the points of the bezier curve (in gui space) without its start
+fn sample_cubic_bezier(control_points: [Pos2; 4], points: &mut Vec<Pos2>) {
+    ///the distance between the samples in gui pixels
+    const SAMPLE_SPACING: f32 = 4.0;
+    const MAX_SAMPLES: usize = 256;
+
+    let [p0, p1, p2, p3] = control_points;
+    //the curve is never longer than its control polygon
+    let length = p0.distance(p1) + p1.distance(p2) + p2.distance(p3);
+    let samples = if length.is_finite() {
+        ((length / SAMPLE_SPACING).ceil() as usize).clamp(1, MAX_SAMPLES)
+    } else {
+        1
+    };
+    for sample in 1..=samples {
+        let t = sample as f32 / samples as f32;
+        let u = 1.0 - t;
+        let point = p0.to_vec2() * (u * u * u)
+            + p1.to_vec2() * (3.0 * u * u * t)
+            + p2.to_vec2() * (3.0 * u * t * t)
+            + p3.to_vec2() * (t * t * t);
+        points.push(point.to_pos2());
+    }
+}
+
+/// where polyline_step jumps between two points
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StepMode {
+    /// jump at the x of the first point so the y of the second point is held before it
+    Before,
+    /// hold the y of the first point and jump at the x of the second point
+    #[default]
+    After,
+    /// jump halfway between the x values of the points
+    Center,
+}
+
+/// how the ends of a line are drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LineCap {
+    /// the line ends exactly at the end point
+    #[default]
+    Butt,
+    /// the line ends with a half circle around the end point
+    Round,
+    /// the line is extended by half the stroke width
+    Square,
+}
+
+/// how the corners of a line are drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LineJoin {
+    /// sharp corners
+    #[default]
+    Miter,
+    /// rounded corners
+    Round,
+}
+
+/// the radius around the cursor in which picking helpers find things
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PickRadiusMode {
+    /// the same radius in gui pixels at every zoom
+    Pixels(f32),
+    /// the same radius in canvas units so it grows on screen when zooming in
+    CanvasUnits(f32),
+}
+
+impl Default for PickRadiusMode {
+    fn default() -> Self {
+        PickRadiusMode::Pixels(5.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use eframe::emath::pos2;
+
+    use super::*;
+    use crate::{PickRadiusMode, Primitive, RecordingPainter};
+
+    #[test]
+    fn clip_segment_keeps_the_part_inside_of_the_rect() {
+        let rect = Rect::from_min_max(pos2(0.0, 0.0), pos2(10.0, 10.0));
+        assert_eq!(
+            clip_segment([pos2(-10.0, 5.0), pos2(30.0, 5.0)], rect),
+            Some((0.25, 0.5))
+        );
+        assert_eq!(
+            clip_segment([pos2(2.0, 2.0), pos2(8.0, 8.0)], rect),
+            Some((0.0, 1.0))
+        );
+        assert_eq!(
+            clip_segment([pos2(-5.0, 20.0), pos2(20.0, 20.0)], rect),
+            None
+        );
+        assert_eq!(clip_segment([pos2(-5.0, 8.0), pos2(8.0, 21.0)], rect), None);
+    }
+
+    #[test]
+    fn points_are_batched_into_a_single_mesh() {
+        let gui_space = Rect::from_min_max(pos2(0.0, 0.0), pos2(100.0, 100.0));
+        let mut painter = RecordingPainter::new(gui_space);
+        let mut cutout = Rect::from_min_max(pos2(0.0, 0.0), pos2(10.0, 10.0));
+        let mut handle = CanvasHandle::new(
+            &mut painter,
+            &mut cutout,
+            gui_space,
+            1.0,
+            Default::default(),
+            PickRadiusMode::default(),
+        );
+        let centers = (0..100).map(|index| Position::Canvas(pos2(index as f32 / 10.0, 5.0)));
+        handle.points(centers, 3.0, Color32::RED);
+
+        match painter.primitives() {
+            [Primitive::Mesh(mesh)] => {
+                //8 triangles for each of the 100 circles
+                assert_eq!(mesh.indices.len(), 100 * 8 * 3);
+            }
+            primitives => panic!("expected a single mesh but got {primitives:?}"),
+        }
+    }
+}
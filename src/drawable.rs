@@ -1,9 +1,11 @@
 use std::cell::RefCell;
-use std::ops::DerefMut;
+use std::ops::{Deref, DerefMut, Range};
 use std::rc::Rc;
 
 use eframe::egui::{Rect, Response as EGuiResponse};
 
+use simple_math::Vec2;
+
 use crate::{CanvasHandle, Position};
 
 pub trait Drawable {
@@ -11,12 +13,46 @@ pub trait Drawable {
 
     fn draw(&mut self, handle: &mut CanvasHandle, draw_data: &Self::DrawData);
 
+    ///draws only the part in the range (e.g. of the time up to a scrub position)
+    ///used instead of draw if the CanvasState has a draw range
+    ///the default ignores the range and draws everything
+    #[allow(unused_variables)]
+    fn draw_range(
+        &mut self,
+        handle: &mut CanvasHandle,
+        draw_data: &Self::DrawData,
+        range: Range<f32>,
+    ) {
+        self.draw(handle, draw_data);
+    }
+
+    ///drawn above draw every frame (e.g. crosshairs and hover highlights)
+    ///if the CanvasState caches the drawn data only this is redrawn on cursor movement
+    #[allow(unused_variables)]
+    fn draw_overlay(&mut self, handle: &mut CanvasHandle, draw_data: &Self::DrawData) {}
+
     fn get_cutout(&mut self, draw_data: &Self::DrawData) -> Rect;
 
     #[allow(unused_variables)]
     fn handle_input(&mut self, response: &Response, handle: &CanvasHandle) {}
+
+    ///Drawables with a higher z_order are drawn above the others
+    ///when they are combined (e.g. in a Vec or a tuple)
+    ///Drawables with the same z_order keep their order
+    ///data is 0, overlays (e.g. CoordinateSystem) are above and backgrounds below
+    ///the ordering is not flattened: a nested Vec or tuple is drawn as one unit at its highest z_order
+    ///so keep backgrounds and overlays in the outermost Vec or tuple to order them against all data
+    fn z_order(&self) -> i32 {
+        0
+    }
 }
 
+///the z_order of overlays that are drawn above the data
+pub const OVERLAY_Z_ORDER: i32 = 100;
+
+///the z_order of backgrounds that are drawn below the data
+pub const BACKGROUND_Z_ORDER: i32 = -100;
+
 impl<T, D> Drawable for &mut T
 where
     T: Drawable<DrawData = D>,
@@ -27,6 +63,19 @@ where
         (*self).draw(handle, draw_data);
     }
 
+    fn draw_range(
+        &mut self,
+        handle: &mut CanvasHandle,
+        draw_data: &Self::DrawData,
+        range: Range<f32>,
+    ) {
+        (*self).draw_range(handle, draw_data, range);
+    }
+
+    fn draw_overlay(&mut self, handle: &mut CanvasHandle, draw_data: &Self::DrawData) {
+        (*self).draw_overlay(handle, draw_data);
+    }
+
     fn get_cutout(&mut self, draw_data: &Self::DrawData) -> Rect {
         (*self).get_cutout(draw_data)
     }
@@ -34,6 +83,10 @@ where
     fn handle_input(&mut self, response: &Response, handle: &CanvasHandle) {
         (*self).handle_input(response, handle);
     }
+
+    fn z_order(&self) -> i32 {
+        (**self).z_order()
+    }
 }
 
 impl<T, D> Drawable for Vec<T>
@@ -43,14 +96,40 @@ where
     type DrawData = D;
 
     fn draw(&mut self, handle: &mut CanvasHandle, draw_data: &Self::DrawData) {
-        for drawable in self {
-            drawable.draw(handle, draw_data);
+        //the sort is stable so Drawables with the same z_order keep their order
+        let mut order: Vec<usize> = (0..self.len()).collect();
+        order.sort_by_key(|index| self[*index].z_order());
+        for index in order {
+            self[index].draw(handle, draw_data);
+        }
+    }
+
+    fn draw_range(
+        &mut self,
+        handle: &mut CanvasHandle,
+        draw_data: &Self::DrawData,
+        range: Range<f32>,
+    ) {
+        let mut order: Vec<usize> = (0..self.len()).collect();
+        order.sort_by_key(|index| self[*index].z_order());
+        for index in order {
+            self[index].draw_range(handle, draw_data, range.clone());
+        }
+    }
+
+    fn draw_overlay(&mut self, handle: &mut CanvasHandle, draw_data: &Self::DrawData) {
+        let mut order: Vec<usize> = (0..self.len()).collect();
+        order.sort_by_key(|index| self[*index].z_order());
+        for index in order {
+            self[index].draw_overlay(handle, draw_data);
         }
     }
 
     fn get_cutout(&mut self, draw_data: &Self::DrawData) -> Rect {
         if let Some(first) = self.first_mut() {
             let mut rect = first.get_cutout(draw_data);
+            //the first drawable is already included
+            //so get_cutout is called exactly once per drawable
             for drawable in self.iter_mut().skip(1) {
                 rect = rect.union(drawable.get_cutout(draw_data));
             }
@@ -67,6 +146,15 @@ where
             drawable.handle_input(response, handle);
         }
     }
+
+    ///the highest z_order so overlays in the Vec stay above the rest
+    ///this lifts the whole Vec (e.g. its data above a sibling with z_order 50 if it holds an overlay)
+    fn z_order(&self) -> i32 {
+        self.iter()
+            .map(|drawable| drawable.z_order())
+            .max()
+            .unwrap_or(0)
+    }
 }
 
 impl Drawable for () {
@@ -91,6 +179,21 @@ where
         borrow.draw(handle, draw_data);
     }
 
+    fn draw_range(
+        &mut self,
+        handle: &mut CanvasHandle,
+        draw_data: &Self::DrawData,
+        range: Range<f32>,
+    ) {
+        let mut borrow = self.borrow_mut();
+        borrow.draw_range(handle, draw_data, range);
+    }
+
+    fn draw_overlay(&mut self, handle: &mut CanvasHandle, draw_data: &Self::DrawData) {
+        let mut borrow = self.borrow_mut();
+        borrow.draw_overlay(handle, draw_data);
+    }
+
     fn get_cutout(&mut self, draw_data: &Self::DrawData) -> Rect {
         let mut borrow = self.borrow_mut();
         borrow.get_cutout(draw_data)
@@ -100,6 +203,10 @@ where
         let mut borrow = self.borrow_mut();
         borrow.handle_input(response, handle);
     }
+
+    fn z_order(&self) -> i32 {
+        self.borrow().z_order()
+    }
 }
 
 impl<T, D> Drawable for Box<T>
@@ -112,6 +219,19 @@ where
         self.deref_mut().draw(handle, draw_data);
     }
 
+    fn draw_range(
+        &mut self,
+        handle: &mut CanvasHandle,
+        draw_data: &Self::DrawData,
+        range: Range<f32>,
+    ) {
+        self.deref_mut().draw_range(handle, draw_data, range);
+    }
+
+    fn draw_overlay(&mut self, handle: &mut CanvasHandle, draw_data: &Self::DrawData) {
+        self.deref_mut().draw_overlay(handle, draw_data);
+    }
+
     fn get_cutout(&mut self, draw_data: &Self::DrawData) -> Rect {
         self.deref_mut().get_cutout(draw_data)
     }
@@ -119,6 +239,10 @@ where
     fn handle_input(&mut self, response: &Response, handle: &CanvasHandle) {
         self.deref_mut().handle_input(response, handle);
     }
+
+    fn z_order(&self) -> i32 {
+        self.deref().z_order()
+    }
 }
 
 impl<T, G, D> Drawable for (T, G)
@@ -129,8 +253,38 @@ where
     type DrawData = D;
 
     fn draw(&mut self, handle: &mut CanvasHandle, draw_data: &Self::DrawData) {
-        self.0.draw(handle, draw_data);
-        self.1.draw(handle, draw_data);
+        if self.1.z_order() < self.0.z_order() {
+            self.1.draw(handle, draw_data);
+            self.0.draw(handle, draw_data);
+        } else {
+            self.0.draw(handle, draw_data);
+            self.1.draw(handle, draw_data);
+        }
+    }
+
+    fn draw_range(
+        &mut self,
+        handle: &mut CanvasHandle,
+        draw_data: &Self::DrawData,
+        range: Range<f32>,
+    ) {
+        if self.1.z_order() < self.0.z_order() {
+            self.1.draw_range(handle, draw_data, range.clone());
+            self.0.draw_range(handle, draw_data, range);
+        } else {
+            self.0.draw_range(handle, draw_data, range.clone());
+            self.1.draw_range(handle, draw_data, range);
+        }
+    }
+
+    fn draw_overlay(&mut self, handle: &mut CanvasHandle, draw_data: &Self::DrawData) {
+        if self.1.z_order() < self.0.z_order() {
+            self.1.draw_overlay(handle, draw_data);
+            self.0.draw_overlay(handle, draw_data);
+        } else {
+            self.0.draw_overlay(handle, draw_data);
+            self.1.draw_overlay(handle, draw_data);
+        }
     }
 
     fn get_cutout(&mut self, draw_data: &Self::DrawData) -> Rect {
@@ -145,18 +299,89 @@ where
         self.0.handle_input(response, handle);
         self.1.handle_input(response, handle);
     }
+
+    ///the higher z_order so an overlay in the tuple stays above the rest
+    ///this lifts the whole tuple like for a Vec
+    fn z_order(&self) -> i32 {
+        self.0.z_order().max(self.1.z_order())
+    }
 }
 
 pub struct Response {
     pub curser_pos: Option<Position>,
     pub clicked: bool,
+    ///the curser position already converted to canvas space
+    pub canvas_pos: Option<Position>,
+    pub dragged: bool,
+    ///the movement of the curser in this frame in canvas space
+    pub drag_delta_canvas: Vec2,
+    ///the vertical scroll of this frame while the canvas is hovered
+    pub scroll_delta: f32,
+    ///the rectangle (in canvas space) the user drags with the selection modifier
+    pub selection: Option<Rect>,
+    ///true in the frame the selection is released
+    ///the selection of this frame is the final one
+    pub selection_committed: bool,
+    pub secondary_clicked: bool,
+    ///the position of the secondary (right) click in canvas space
+    ///e.g. to open a context menu for a data coordinate
+    pub secondary_click_pos: Option<Position>,
 }
 
 impl From<&EGuiResponse> for Response {
+    ///the canvas space fields are left empty
     fn from(response: &EGuiResponse) -> Self {
         Response {
             curser_pos: response.hover_pos().map(Position::Gui),
             clicked: response.clicked(),
+            canvas_pos: None,
+            dragged: response.dragged(),
+            drag_delta_canvas: Vec2::new(0.0, 0.0),
+            scroll_delta: 0.0,
+            selection: None,
+            selection_committed: false,
+            secondary_clicked: response.secondary_clicked(),
+            secondary_click_pos: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use eframe::emath::pos2;
+
+    use super::*;
+
+    ///counts the calls of get_cutout
+    struct CutoutCounter {
+        cutout: Rect,
+        calls: usize,
+    }
+
+    impl Drawable for CutoutCounter {
+        type DrawData = ();
+
+        fn draw(&mut self, _handle: &mut CanvasHandle, _draw_data: &()) {}
+
+        fn get_cutout(&mut self, _draw_data: &()) -> Rect {
+            self.calls += 1;
+            self.cutout
         }
     }
+
+    #[test]
+    fn vec_get_cutout_queries_every_drawable_once() {
+        let mut drawables: Vec<CutoutCounter> = [(0.0, 1.0), (-2.0, 0.5), (3.0, 4.0)]
+            .into_iter()
+            .map(|(min, max)| CutoutCounter {
+                cutout: Rect::from_min_max(pos2(min, min), pos2(max, max)),
+                calls: 0,
+            })
+            .collect();
+
+        let cutout = drawables.get_cutout(&());
+
+        assert_eq!(cutout, Rect::from_min_max(pos2(-2.0, -2.0), pos2(4.0, 4.0)));
+        assert!(drawables.iter().all(|drawable| drawable.calls == 1));
+    }
 }
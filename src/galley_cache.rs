@@ -0,0 +1,57 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use eframe::egui::{Color32, Ui};
+use eframe::epaint::{FontFamily, FontId, Galley};
+
+#[derive(Debug, PartialEq, Eq, Hash)]
+struct GalleyKey {
+    text: String,
+    family: FontFamily,
+    ///FontId is not Eq because of the f32 size
+    size_bits: u32,
+}
+
+///caches the text layouts of the canvas between frames
+///layouts that were not used in the last frame are dropped
+#[derive(Debug, Default)]
+pub(crate) struct GalleyCache {
+    pixels_per_point: f32,
+    current_frame: HashMap<GalleyKey, Arc<Galley>>,
+    last_frame: HashMap<GalleyKey, Arc<Galley>>,
+}
+
+impl GalleyCache {
+    pub(crate) fn new_frame(&mut self, pixels_per_point: f32) {
+        self.last_frame = std::mem::take(&mut self.current_frame);
+
+        //the galleys depend on the font atlas which is rebuilt if the pixels_per_point change
+        if pixels_per_point != self.pixels_per_point {
+            self.pixels_per_point = pixels_per_point;
+            self.last_frame.clear();
+        }
+    }
+
+    ///the color of the galley is just a dummy value
+    ///so it has to be painted with a color (e.g. with galley_with_color)
+    pub(crate) fn layout(&mut self, ui: &Ui, text: String, font_id: FontId) -> Arc<Galley> {
+        let key = GalleyKey {
+            text,
+            family: font_id.family.clone(),
+            size_bits: font_id.size.to_bits(),
+        };
+
+        if let Some(galley) = self.current_frame.get(&key) {
+            return galley.clone();
+        }
+
+        let galley = match self.last_frame.remove(&key) {
+            Some(galley) => galley,
+            None => ui
+                .painter()
+                .layout_no_wrap(key.text.clone(), font_id, Color32::BLACK),
+        };
+        self.current_frame.insert(key, galley.clone());
+        galley
+    }
+}
@@ -1,28 +1,157 @@
+use std::collections::HashMap;
+use std::ops::Range;
+
 use eframe::egui::Vec2 as GuiVec;
-use eframe::egui::{vec2, Color32, Key, Rect, Response as EguiResponse, Sense, Ui, Widget};
+use eframe::egui::{
+    pos2, vec2, Align2, Color32, ColorImage, Context, CursorIcon, Key, Modifiers, PointerButton,
+    Pos2, Rect, Response as EguiResponse, Sense, Stroke, Ui, Widget,
+};
 
-use eframe::epaint::{FontId, Rounding};
+use eframe::epaint::{FontId, Rounding, Shape};
 
 mod canvas_handle;
 mod drawable;
+mod galley_cache;
+mod painter;
 mod position;
+mod raster;
+mod recording;
+pub mod svg;
+mod transform;
 
 mod utility {
+    pub mod background_image;
     pub mod coordinate_system;
+    pub mod crosshair;
+    pub mod cursor_inspector;
+    pub mod decimate;
+    pub mod legend;
+    pub mod line_with_band;
+    pub mod perf_overlay;
+    pub mod region_input;
+    pub mod ruler;
+    pub mod ruler_grid;
+    pub mod time_axis;
+    pub mod transformed;
 }
 
+use galley_cache::GalleyCache;
+use painter::EguiPainter;
+use raster::RasterPainter;
 use simple_math::{Rectangle, Vec2};
-pub use utility::coordinate_system::{Alignment, Axis, CoordinateSystem, Placement, Tick};
+use svg::SvgPainter;
+pub use utility::background_image::BackgroundImage;
+pub use utility::coordinate_system::{
+    Alignment, Axis, CoordinateSystem, LineStyle, Placement, Tick, TickSide,
+};
+pub use utility::crosshair::Crosshair;
+pub use utility::cursor_inspector::{interpolate_y, CursorInspector, InspectedSeries};
+pub use utility::decimate::decimate;
+pub use utility::legend::Legend;
+pub use utility::line_with_band::LineWithBand;
+pub use utility::perf_overlay::PerfOverlay;
+pub use utility::region_input::RegionInput;
+pub use utility::ruler::Ruler;
+pub use utility::ruler_grid::RulerGrid;
+pub use utility::transformed::Transformed;
 
-pub use canvas_handle::CanvasHandle;
-pub use drawable::{Drawable, Response};
-pub use position::Position;
+pub use canvas_handle::{CanvasHandle, LineCap, LineJoin, MarkerShape, PickRadiusMode, StepMode};
+pub use drawable::{Drawable, Response, BACKGROUND_Z_ORDER, OVERLAY_Z_ORDER};
+pub use painter::Painter;
+pub use position::{Margins, Position};
+pub use recording::{Primitive, RecordingPainter};
+pub use transform::Transform;
 
 pub struct CanvasState {
     current_cutout: Rect,
     mode: CanvasMode,
     draw_frame: bool,
+    frame_stroke: Stroke,
+    aspect_ratio: f32,
+    ///the smallest space between the drawn cutout and each border of the canvas
+    margins: Margins,
+    fill_mode: FillMode,
+    pick_radius_mode: PickRadiusMode,
+    min_cutout_size: Vec2,
+    max_cutout_size: Vec2,
+    box_zoom_modifier: Modifiers,
+    selection_modifier: Option<Modifiers>,
+    pan_button: PointerButton,
+    hover_cursor_icon: Option<CursorIcon>,
+    drag_cursor_icon: Option<CursorIcon>,
+    box_zoom_match_aspect: bool,
+    constrain_pan_modifier: Option<Modifiers>,
+    zoom_sensitivity: f32,
+    scroll_behavior: ScrollBehavior,
+    keyboard_pan_enabled: bool,
+    ///the fraction of the cutout that is panned per second
+    keyboard_pan_speed: f32,
+    double_click_action: Option<DoubleClickAction>,
+    repaint_on_hover: bool,
+    sense: Sense,
+    galley_cache: GalleyCache,
+    pan_bounds: Option<Rect>,
+    pan_bounds_fraction: f32,
+    ///the time of the last frame
+    time: f64,
+    ///start times of the timers
+    timers: HashMap<String, f64>,
+    ///the duration of the zoom and reset transitions in seconds
+    animation_duration: Option<f32>,
+    animation: Option<CutoutAnimation>,
+    ///the velocity of the cutout after a drag in canvas units per second
+    pan_velocity: GuiVec,
+    ///the cutout keeps gliding after a drag is released
+    pan_inertia: bool,
+    ///the fraction of the pan velocity that is left after one second
+    inertia_decay_per_second: f32,
+    ///the glide stops below this velocity in gui pixels per second
+    inertia_min_velocity: f32,
+    ///the range the drawables are drawn in with draw_range
+    draw_range: Option<Range<f32>>,
+    cache_static: bool,
+    static_cache: Option<StaticCache>,
+    ///the key of the last frame that was drawn without the cache
+    last_cache_key: Option<CacheKey>,
+}
+
+///the shapes of the drawn data that are painted again while the view does not change
+struct StaticCache {
+    key: CacheKey,
+    ///the top left of the canvas when the shapes were recorded
+    origin: Pos2,
+    ///the shapes with their clip rect in gui space
+    shapes: Vec<(Rect, Shape)>,
+}
+
+///what the static cache does in a frame
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CacheAction {
+    ///the cached shapes are painted instead of drawing the data
+    Replay,
+    ///the data is drawn and recorded into the cache
+    Record(CacheKey),
+    ///the data is drawn without the cache
+    Draw,
+}
+
+///everything that changes what the data looks like besides the data itself
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct CacheKey {
+    cutout: Rect,
+    size: GuiVec,
     aspect_ratio: f32,
+    margins: Margins,
+    pixels_per_point: f32,
+    dark_mode: bool,
+}
+
+///a transition of the cutout started by a zoom, reset or set_cutout
+#[derive(Debug, Clone, Copy)]
+struct CutoutAnimation {
+    from: Rect,
+    to: Rect,
+    start_time: f64,
 }
 
 impl CanvasState {
@@ -35,7 +164,42 @@ impl CanvasState {
             current_cutout: default_cutout,
             mode: Normal,
             draw_frame: false,
+            frame_stroke: Stroke::new(5.0, Color32::DARK_RED),
             aspect_ratio: 1.0,
+            margins: Margins::default(),
+            fill_mode: FillMode::PreserveAspect,
+            pick_radius_mode: PickRadiusMode::default(),
+            min_cutout_size: Vec2::new(0.0, 0.0),
+            max_cutout_size: Vec2::new(f32::INFINITY, f32::INFINITY),
+            box_zoom_modifier: Modifiers::CTRL,
+            selection_modifier: None,
+            pan_button: PointerButton::Primary,
+            hover_cursor_icon: Some(CursorIcon::Grab),
+            drag_cursor_icon: Some(CursorIcon::Grabbing),
+            box_zoom_match_aspect: false,
+            constrain_pan_modifier: None,
+            zoom_sensitivity: 1.0,
+            scroll_behavior: ScrollBehavior::Zoom,
+            keyboard_pan_enabled: true,
+            keyboard_pan_speed: 0.5,
+            double_click_action: None,
+            repaint_on_hover: true,
+            sense: Sense::click_and_drag(),
+            galley_cache: GalleyCache::default(),
+            pan_bounds: None,
+            pan_bounds_fraction: 0.1,
+            time: 0.0,
+            timers: HashMap::new(),
+            animation_duration: None,
+            animation: None,
+            pan_velocity: GuiVec::ZERO,
+            pan_inertia: false,
+            inertia_decay_per_second: 0.05,
+            inertia_min_velocity: 10.0,
+            draw_range: None,
+            cache_static: false,
+            static_cache: None,
+            last_cache_key: None,
         }
     }
 
@@ -44,33 +208,763 @@ impl CanvasState {
         self
     }
 
+    ///draws a frame around the canvas
+    ///default is false
+    pub fn set_draw_frame(&mut self, draw_frame: bool) {
+        self.draw_frame = draw_frame;
+    }
+
+    ///the color and width of the frame
+    ///default is 5.0 wide and dark red
+    pub fn set_frame_stroke(&mut self, frame_stroke: impl Into<Stroke>) {
+        self.frame_stroke = frame_stroke.into();
+    }
+
+    pub fn aspect_ratio(&self) -> f32 {
+        self.aspect_ratio
+    }
+
+    ///true while the user pans the canvas by dragging
+    pub fn is_dragging(&self) -> bool {
+        self.mode == CanvasMode::Dragging
+    }
+
+    ///true while the user drags the canvas in any way (panning, box zoom or selecting)
+    pub fn is_interacting(&self) -> bool {
+        self.mode != CanvasMode::Normal
+    }
+
     pub fn set_aspect_ratio(&mut self, aspect_ratio: f32) {
         self.aspect_ratio = aspect_ratio;
     }
 
+    ///the smallest space between the drawn cutout and the border of the canvas in gui pixels
+    ///e.g. more for long tick labels or less for dense dashboards
+    ///default is 20.0
+    pub fn set_min_padding(&mut self, min_padding: f32) {
+        self.margins = Margins::same(min_padding.max(0.0));
+    }
+
+    ///like set_min_padding but for every side of the canvas on its own
+    ///default is 20.0 on every side
+    pub fn set_margins(&mut self, margins: Margins) {
+        self.margins = margins;
+    }
+
+    ///default is FillMode::PreserveAspect
+    pub fn set_fill_mode(&mut self, fill_mode: FillMode) {
+        self.fill_mode = fill_mode;
+    }
+
+    ///the radius picking helpers of the CanvasHandle use
+    ///default is PickRadiusMode::Pixels(5.0)
+    pub fn set_pick_radius_mode(&mut self, pick_radius_mode: PickRadiusMode) {
+        self.pick_radius_mode = pick_radius_mode;
+    }
+
+    ///the aspect ratio used for the conversions between the spaces
+    ///in Stretch mode it is chosen so the cutout fills the whole gui_space
+    fn effective_aspect_ratio(&self, gui_space: Rect) -> f32 {
+        match self.fill_mode {
+            FillMode::PreserveAspect => self.aspect_ratio,
+            FillMode::Stretch => {
+                let aspect_ratio = Position::calculate_stretch_aspect_ratio(
+                    gui_space,
+                    self.current_cutout,
+                    self.margins,
+                );
+                if aspect_ratio.is_finite() && aspect_ratio > 0.0 {
+                    aspect_ratio
+                } else {
+                    self.aspect_ratio
+                }
+            }
+        }
+    }
+
+    ///the smallest size the cutout can be zoomed to (in canvas space)
+    ///default is unbounded (0.0, 0.0)
+    pub fn set_min_cutout_size(&mut self, min_cutout_size: Vec2) {
+        self.min_cutout_size = min_cutout_size;
+    }
+
+    ///the biggest size the cutout can be zoomed to (in canvas space)
+    ///default is unbounded (infinity, infinity)
+    pub fn set_max_cutout_size(&mut self, max_cutout_size: Vec2) {
+        self.max_cutout_size = max_cutout_size;
+    }
+
+    ///the modifier that has to be held while dragging to zoom into the dragged rectangle
+    ///default is Ctrl
+    pub fn set_box_zoom_modifier(&mut self, box_zoom_modifier: Modifiers) {
+        self.box_zoom_modifier = box_zoom_modifier;
+    }
+
+    ///the modifier that has to be held while dragging to select a rectangle
+    ///the selection is passed to the Drawables in the Response
+    ///default is None for no selection
+    pub fn set_selection_modifier(&mut self, selection_modifier: Option<Modifiers>) {
+        self.selection_modifier = selection_modifier;
+    }
+
+    ///the mouse button that pans the canvas by dragging
+    ///box zoom always uses the primary button with the box zoom modifier
+    ///so with e.g. PointerButton::Middle the primary button is free for selections
+    ///default is PointerButton::Primary
+    pub fn set_pan_button(&mut self, pan_button: PointerButton) {
+        self.pan_button = pan_button;
+    }
+
+    ///the cursor icon while the canvas is hovered and can be panned
+    ///Drawables can still set another icon with CanvasHandle::set_cursor
+    ///default is Some(CursorIcon::Grab) None for the default icon
+    pub fn set_hover_cursor_icon(&mut self, hover_cursor_icon: Option<CursorIcon>) {
+        self.hover_cursor_icon = hover_cursor_icon;
+    }
+
+    ///the cursor icon while the canvas is panned by dragging
+    ///default is Some(CursorIcon::Grabbing) None for the default icon
+    pub fn set_drag_cursor_icon(&mut self, drag_cursor_icon: Option<CursorIcon>) {
+        self.drag_cursor_icon = drag_cursor_icon;
+    }
+
+    ///if true the box zoom rectangle is expanded around its center to the aspect of the view
+    ///so the zoomed view shows exactly the expanded rectangle without letterbox
+    ///default is false for zooming into the exact selection
+    pub fn set_box_zoom_match_aspect(&mut self, box_zoom_match_aspect: bool) {
+        self.box_zoom_match_aspect = box_zoom_match_aspect;
+    }
+
+    ///the rect expanded around its center so it fills gui_space without letterbox
+    fn expand_to_view_aspect(&self, rect: Rect, gui_space: Rect) -> Rect {
+        if self.fill_mode == FillMode::Stretch {
+            //there is no letterbox in Stretch mode
+            return rect;
+        }
+        //how much wider the rect has to be to fill gui_space
+        let widen_factor = Position::calculate_stretch_aspect_ratio(gui_space, rect, self.margins)
+            / self.effective_aspect_ratio(gui_space);
+        if !widen_factor.is_finite() || widen_factor <= 0.0 {
+            return rect;
+        }
+        let mut size = rect.size();
+        if widen_factor > 1.0 {
+            size.x *= widen_factor;
+        } else {
+            size.y /= widen_factor;
+        }
+        Rect::from_center_size(rect.center(), size)
+    }
+
+    ///while the modifier is held dragging only pans along the dominant axis
+    ///default is None for no constrained panning
+    pub fn set_constrain_pan_modifier(&mut self, constrain_pan_modifier: Option<Modifiers>) {
+        self.constrain_pan_modifier = constrain_pan_modifier;
+    }
+
+    ///scales how much one mouse wheel step zooms
+    ///with the default of 1.0 one step shrinks the cutout to 90%
+    ///values below 1.0 zoom slower (e.g. for trackpads)
+    pub fn set_zoom_sensitivity(&mut self, zoom_sensitivity: f32) {
+        self.zoom_sensitivity = zoom_sensitivity.max(0.0);
+    }
+
+    ///what scrolling over the canvas does
+    ///default is ScrollBehavior::Zoom
+    pub fn set_scroll_behavior(&mut self, scroll_behavior: ScrollBehavior) {
+        self.scroll_behavior = scroll_behavior;
+    }
+
+    ///the zoom factor of one mouse wheel step
+    fn zoom_step(&self) -> f32 {
+        //0.9 means that the new cutout is 90% of the old cutout
+        0.9_f32.powf(self.zoom_sensitivity)
+    }
+
+    ///if true the arrow keys pan the cutout while the canvas is hovered
+    ///default is true
+    pub fn set_keyboard_pan_enabled(&mut self, keyboard_pan_enabled: bool) {
+        self.keyboard_pan_enabled = keyboard_pan_enabled;
+    }
+
+    ///the fraction of the cutout that is panned per second while an arrow key is held
+    ///default is 0.5
+    pub fn set_keyboard_pan_speed(&mut self, keyboard_pan_speed: f32) {
+        self.keyboard_pan_speed = keyboard_pan_speed;
+    }
+
+    ///what happens if the user double clicks the canvas None for nothing
+    ///default is None
+    pub fn set_double_click_action(&mut self, double_click_action: Option<DoubleClickAction>) {
+        self.double_click_action = double_click_action;
+    }
+
+    ///if false the cursor readout is only drawn while the user drags
+    ///so static scenes do not change when the pointer moves over the canvas
+    ///default is true
+    pub fn set_repaint_on_hover(&mut self, repaint_on_hover: bool) {
+        self.repaint_on_hover = repaint_on_hover;
+    }
+
+    ///the drawables are drawn with draw_range in the range (e.g. up to a scrub position)
+    ///default is None for drawing everything with draw
+    pub fn set_draw_range(&mut self, draw_range: Option<Range<f32>>) {
+        if self.draw_range != draw_range {
+            self.draw_range = draw_range;
+            self.mark_dirty();
+        }
+    }
+
+    ///if true the shapes of the drawn data are cached while the cutout and size do not change
+    ///so moving the cursor only redraws the overlay (draw_overlay and the cursor readout)
+    ///draw is not called while the cache is used so animations, hover ui and repaint requests
+    ///belong into draw_overlay
+    ///call mark_dirty if the data changes
+    ///default is false
+    pub fn set_cache_static(&mut self, cache_static: bool) {
+        self.cache_static = cache_static;
+        if !cache_static {
+            self.mark_dirty();
+        }
+    }
+
+    ///the cached data is drawn again in the next frame
+    pub fn mark_dirty(&mut self) {
+        self.static_cache = None;
+        self.last_cache_key = None;
+    }
+
+    ///what egui reports for the canvas
+    ///without drag panning, box zoom and double clicks never happen (e.g. Sense::hover())
+    ///zooming with the mouse wheel and keyboard panning only need hover
+    ///so they are still turned off by set_zoom_sensitivity(0.0) and set_keyboard_pan_enabled
+    ///default is Sense::click_and_drag()
+    pub fn set_sense(&mut self, sense: Sense) {
+        self.sense = sense;
+    }
+
+    ///the region (in canvas space) the cutout can not leave by panning or zooming
+    ///default is None for no bounds
+    pub fn set_pan_bounds(&mut self, pan_bounds: Option<Rect>) {
+        self.pan_bounds = pan_bounds;
+    }
+
+    ///the fraction of the cutout (or the pan bounds if they are smaller)
+    ///that has to stay inside of the pan bounds in every dimension
+    ///default is 0.1
+    pub fn set_pan_bounds_fraction(&mut self, pan_bounds_fraction: f32) {
+        self.pan_bounds_fraction = pan_bounds_fraction.clamp(0.0, 1.0);
+    }
+
+    ///starts (or restarts) the timer with the name at the time of the last frame
+    pub fn start_timer(&mut self, name: impl Into<String>) {
+        self.timers.insert(name.into(), self.time);
+    }
+
+    pub fn stop_timer(&mut self, name: &str) {
+        self.timers.remove(name);
+    }
+
+    ///the seconds since the timer with the name was started
+    ///None if there is no such timer
+    pub fn elapsed(&self, name: &str) -> Option<f32> {
+        self.timers
+            .get(name)
+            .map(|start| (self.time - start) as f32)
+    }
+
+    ///animate zooming, resetting and set_cutout over the duration in seconds
+    ///default is None for instant changes
+    pub fn set_animation_duration(&mut self, animation_duration: Option<f32>) {
+        self.animation_duration = animation_duration;
+    }
+
+    ///tunes the glide of set_pan_inertia
+    ///decay_per_second is the fraction of the velocity that is left after one second
+    ///the glide stops if the velocity drops below min_velocity (in gui pixels per second)
+    ///default is a decay_per_second of 0.05 and a min_velocity of 10.0
+    pub fn set_inertia_params(&mut self, decay_per_second: f32, min_velocity: f32) {
+        self.inertia_decay_per_second = decay_per_second.clamp(0.0, 1.0);
+        self.inertia_min_velocity = min_velocity;
+    }
+
+    ///the cutout keeps gliding after a drag is released
+    ///any new interaction stops the glide
+    ///default is false
+    pub fn set_pan_inertia(&mut self, pan_inertia: bool) {
+        self.pan_inertia = pan_inertia;
+        self.pan_velocity = GuiVec::ZERO;
+    }
+
+    ///the camera (cutout, aspect ratio and limits) to persist the view
+    ///the cutout is the target of a running animation
+    pub fn view_state(&self) -> ViewState {
+        let max_cutout_size = (self.max_cutout_size.x().is_finite()
+            && self.max_cutout_size.y().is_finite())
+        .then_some([self.max_cutout_size.x(), self.max_cutout_size.y()]);
+        ViewState {
+            cutout: rect_to_array(self.target_cutout()),
+            aspect_ratio: self.aspect_ratio,
+            fill_mode: self.fill_mode,
+            pan_bounds: self.pan_bounds.map(rect_to_array),
+            min_cutout_size: [self.min_cutout_size.x(), self.min_cutout_size.y()],
+            max_cutout_size,
+        }
+    }
+
+    ///restores a camera saved with view_state
+    ///the cutout is set instantly even if an animation duration is set
+    pub fn restore_view_state(&mut self, view_state: ViewState) {
+        let [min_x, min_y] = view_state.min_cutout_size;
+        let [max_x, max_y] = view_state
+            .max_cutout_size
+            .unwrap_or([f32::INFINITY, f32::INFINITY]);
+        self.aspect_ratio = view_state.aspect_ratio;
+        self.fill_mode = view_state.fill_mode;
+        self.pan_bounds = view_state.pan_bounds.map(array_to_rect);
+        self.min_cutout_size = Vec2::new(min_x, min_y);
+        self.max_cutout_size = Vec2::new(max_x, max_y);
+        self.animation = None;
+        self.pan_velocity = GuiVec::ZERO;
+        self.current_cutout = sanitize_cutout(array_to_rect(view_state.cutout));
+    }
+
+    ///the cutout at the end of the running animation or the current cutout
+    fn target_cutout(&self) -> Rect {
+        match self.animation {
+            Some(animation) => animation.to,
+            None => self.current_cutout,
+        }
+    }
+
+    ///moves the cutout to target either instantly or animated
+    fn set_target_cutout(&mut self, target: Rect) {
+        let target = sanitize_cutout(target);
+        self.pan_velocity = GuiVec::ZERO;
+        match self.animation_duration {
+            Some(duration) if duration > 0.0 => {
+                self.animation = Some(CutoutAnimation {
+                    from: self.current_cutout,
+                    to: target,
+                    start_time: self.time,
+                });
+            }
+            _ => {
+                self.animation = None;
+                self.current_cutout = target;
+            }
+        }
+    }
+
+    ///advances the running animation to the time of the current frame
+    ///returns true if the animation is not finished
+    fn animate(&mut self) -> bool {
+        let animation = match self.animation {
+            Some(animation) => animation,
+            None => return false,
+        };
+        let duration = self.animation_duration.unwrap_or(0.0);
+        let progress = if duration > 0.0 {
+            ((self.time - animation.start_time) as f32 / duration).clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
+
+        //ease out so the transition slows down towards the target
+        let t = 1.0 - (1.0 - progress).powi(3);
+        let lerp = |from: Pos2, to: Pos2| from + (to - from) * t;
+        self.current_cutout = Rect::from_min_max(
+            lerp(animation.from.min, animation.to.min),
+            lerp(animation.from.max, animation.to.max),
+        );
+
+        if progress >= 1.0 {
+            self.animation = None;
+            false
+        } else {
+            true
+        }
+    }
+
+    ///translates the cutout so that it does not leave the pan bounds
+    fn clamp_to_pan_bounds(&self, cutout: Rect) -> Rect {
+        let bounds = match self.pan_bounds {
+            Some(bounds) => bounds,
+            None => return cutout,
+        };
+
+        //the translation needed in one dimension
+        let clamp = |cutout_min: f32, cutout_max: f32, bounds_min: f32, bounds_max: f32| {
+            let cutout_size = cutout_max - cutout_min;
+            let bounds_size = bounds_max - bounds_min;
+            let needed_overlap = self.pan_bounds_fraction * cutout_size.min(bounds_size);
+            if cutout_min > bounds_max - needed_overlap {
+                bounds_max - needed_overlap - cutout_min
+            } else if cutout_max < bounds_min + needed_overlap {
+                bounds_min + needed_overlap - cutout_max
+            } else {
+                0.0
+            }
+        };
+        let translation = GuiVec {
+            x: clamp(cutout.min.x, cutout.max.x, bounds.min.x, bounds.max.x),
+            y: clamp(cutout.min.y, cutout.max.y, bounds.min.y, bounds.max.y),
+        };
+        cutout.translate(translation)
+    }
+
+    ///scales the cutout by zoom_factor so that fix_point (in canvas space) keeps its relative position
+    ///the zoom is rejected if it would cross a zoom limit
+    fn zoom_around(&mut self, fix_point: GuiVec, zoom_factor: GuiVec) {
+        //zooming during an animation continues from its target
+        let cutout = self.target_cutout();
+        let inverse_zoom_factor = GuiVec::splat(1.0) - zoom_factor;
+
+        //the offset is calculated so the fix_point keeps its relative position
+        let offset = fix_point * inverse_zoom_factor + zoom_factor * cutout.min.to_vec2();
+
+        let new_cutout = Rect::from_min_size(offset.to_pos2(), cutout.size() * zoom_factor);
+
+        if self.cutout_size_allowed(new_cutout) {
+            self.set_target_cutout(self.clamp_to_pan_bounds(new_cutout));
+        }
+    }
+
+    fn cutout_size_allowed(&self, cutout: Rect) -> bool {
+        let size = cutout.size();
+        size.x >= self.min_cutout_size.x()
+            && size.y >= self.min_cutout_size.y()
+            && size.x <= self.max_cutout_size.x()
+            && size.y <= self.max_cutout_size.y()
+    }
+
     fn reset_cutout<D, E>(&mut self, drawable: &mut E, draw_data: &D)
     where
         E: Drawable<DrawData = D>,
     {
-        self.current_cutout = drawable.get_cutout(draw_data);
+        self.set_target_cutout(drawable.get_cutout(draw_data));
+    }
+
+    ///the cutout of the drawable (the bounds of its data) without changing the current cutout
+    ///takes the drawable mutably because Drawable::get_cutout does
+    pub fn data_bounds<D, E>(&self, drawable: &mut E, draw_data: &D) -> Rect
+    where
+        E: Drawable<DrawData = D>,
+    {
+        drawable.get_cutout(draw_data)
+    }
+
+    ///like reset_cutout but the cutout of the drawable is expanded
+    ///by margin_fraction of its size on every side
+    ///so points on the edge of the data are not drawn on the edge of the canvas
+    pub fn fit_to_data<D, E>(&mut self, drawable: &mut E, draw_data: &D, margin_fraction: f32)
+    where
+        E: Drawable<DrawData = D>,
+    {
+        let bounds = drawable.get_cutout(draw_data);
+        self.fit_to(bounds, margin_fraction);
+    }
+
+    ///sets the cutout to the bounds (in canvas space)
+    ///expanded by margin as a fraction of the size of bounds on every side
+    ///a dimension without extend keeps the size of the current cutout
+    pub fn fit_to(&mut self, bounds: Rect, margin: f32) {
+        if !bounds.is_finite() || bounds.is_negative() {
+            return;
+        }
+        let mut size = bounds.size() * (1.0 + 2.0 * margin);
+        if size.x <= 0.0 {
+            size.x = self.target_cutout().width();
+        }
+        if size.y <= 0.0 {
+            size.y = self.target_cutout().height();
+        }
+        self.set_target_cutout(Rect::from_center_size(bounds.center(), size));
+    }
+
+    ///returns true if the content of the drawable intersects the region that is visible in gui_space
+    ///returns false if the drawable has no content (e.g. Rect::NOTHING)
+    pub fn content_visible<D, E>(
+        &mut self,
+        drawable: &mut E,
+        draw_data: &D,
+        gui_space: Rect,
+    ) -> bool
+    where
+        E: Drawable<DrawData = D>,
+    {
+        let content = drawable.get_cutout(draw_data);
+        if !content.is_finite() || content.is_negative() {
+            return false;
+        }
+        self.visible_region(gui_space).intersects(content)
+    }
+
+    ///the fraction of the content of the drawable that is visible in gui_space
+    ///returns 1.0 if the content has no area
+    pub fn visible_fraction<D, E>(
+        &mut self,
+        drawable: &mut E,
+        draw_data: &D,
+        gui_space: Rect,
+    ) -> f32
+    where
+        E: Drawable<DrawData = D>,
+    {
+        let content = drawable.get_cutout(draw_data);
+        if !content.is_finite() || !content.is_positive() {
+            return 1.0;
+        }
+        let visible_content = self.visible_region(gui_space).intersect(content);
+        if !visible_content.is_positive() {
+            return 0.0;
+        }
+        (visible_content.area() / content.area()).clamp(0.0, 1.0)
+    }
+
+    ///renders the drawable into an svg of the given size
+    ///the svg shows the same as a canvas of this size on screen
+    pub fn export_svg<D, E>(&mut self, drawable: &mut E, draw_data: &D, size: Vec2) -> String
+    where
+        E: Drawable<DrawData = D>,
+    {
+        let gui_space = Rect::from_min_size(Pos2::ZERO, size.into());
+        let mut painter = SvgPainter::new(gui_space);
+        self.draw_with_painter(drawable, draw_data, gui_space, &mut painter);
+
+        painter.finish()
+    }
+
+    ///copies the drawable as svg text of the given size to the clipboard
+    ///images can not be copied because the clipboard of egui 0.18 only holds text
+    ///use render_to_image to get the pixels instead
+    pub fn copy_svg_to_clipboard<D, E>(
+        &mut self,
+        drawable: &mut E,
+        draw_data: &D,
+        ctx: &Context,
+        size: Vec2,
+    ) where
+        E: Drawable<DrawData = D>,
+    {
+        let svg = self.export_svg(drawable, draw_data, size);
+        ctx.output().copied_text = svg;
+    }
+
+    ///renders the drawable offscreen into an image of the given size in pixels
+    ///the image shows the same as a canvas of this size on screen
+    ///independent of the size of the widget (e.g. for thumbnails)
+    pub fn render_to_image<D, E>(
+        &mut self,
+        drawable: &mut E,
+        draw_data: &D,
+        size: Vec2,
+    ) -> ColorImage
+    where
+        E: Drawable<DrawData = D>,
+    {
+        let gui_space = Rect::from_min_size(Pos2::ZERO, size.into());
+        let mut painter = RasterPainter::new(gui_space, 1.0, false);
+        self.draw_with_painter(drawable, draw_data, gui_space, &mut painter);
+
+        painter.finish()
+    }
+
+    ///draws the drawable and serializes the painted shapes to json
+    ///the positions are in canvas space
+    #[cfg(feature = "serde")]
+    pub fn dump_shapes_json<D, E>(
+        &mut self,
+        drawable: &mut E,
+        draw_data: &D,
+        gui_space: Rect,
+    ) -> String
+    where
+        E: Drawable<DrawData = D>,
+    {
+        let mut painter = RecordingPainter::new(gui_space);
+        self.draw_with_painter(drawable, draw_data, gui_space, &mut painter);
+
+        let shapes: Vec<_> = painter
+            .primitives()
+            .iter()
+            .filter_map(|primitive| {
+                primitive.dump(|pos| {
+                    Position::Gui(pos).to_canvas_space(
+                        gui_space,
+                        self.current_cutout,
+                        self.effective_aspect_ratio(gui_space),
+                        self.margins,
+                    )
+                })
+            })
+            .collect();
+        serde_json::to_string_pretty(&shapes).expect("dumped shapes are always serializable")
+    }
+
+    ///draws the drawable with the painter as if the canvas was placed at gui_space
+    ///this allows to run Drawables headless (e.g. with a RecordingPainter)
+    pub fn draw_with_painter<D, E>(
+        &mut self,
+        drawable: &mut E,
+        draw_data: &D,
+        gui_space: Rect,
+        painter: &mut dyn Painter,
+    ) where
+        E: Drawable<DrawData = D>,
+    {
+        let aspect_ratio = self.effective_aspect_ratio(gui_space);
+        //a copy so drawing headless does not change the view of the canvas
+        let mut cutout = self.current_cutout;
+        let mut canvas_handle = CanvasHandle::new(
+            painter,
+            &mut cutout,
+            gui_space,
+            aspect_ratio,
+            self.margins,
+            self.pick_radius_mode,
+        );
+        draw_in_range(
+            drawable,
+            &mut canvas_handle,
+            draw_data,
+            self.draw_range.clone(),
+        );
+    }
+
+    ///the region in canvas space that is visible in gui_space
+    ///in general, this is not equal to the current cutout
+    ///but bigger in one dimension
+    fn visible_region(&self, gui_space: Rect) -> Rect {
+        let corner_a = Position::Gui(gui_space.min).to_canvas_space(
+            gui_space,
+            self.current_cutout,
+            self.effective_aspect_ratio(gui_space),
+            self.margins,
+        );
+        let corner_b = Position::Gui(gui_space.max).to_canvas_space(
+            gui_space,
+            self.current_cutout,
+            self.effective_aspect_ratio(gui_space),
+            self.margins,
+        );
+        Rect::from_two_pos(corner_a, corner_b)
     }
 
     fn center_cutout(&mut self, center: Vec2) {
+        self.pan_velocity = GuiVec::ZERO;
         let old_center: Vec2 = self.current_cutout.center().into();
         let translation = center - old_center;
+        self.animation = None;
         self.current_cutout = self.current_cutout.translate(translation.into());
     }
 }
 
+///draws the drawable with draw_range if there is a range and with draw otherwise
+fn draw_in_range<D, E>(
+    drawable: &mut E,
+    handle: &mut CanvasHandle,
+    draw_data: &D,
+    draw_range: Option<Range<f32>>,
+) where
+    E: Drawable<DrawData = D>,
+{
+    match draw_range {
+        Some(range) => drawable.draw_range(handle, draw_data, range),
+        None => drawable.draw(handle, draw_data),
+    }
+}
+
+///a cutout that can be converted to gui space
+///non finite or empty cutouts (e.g. Rect::NOTHING) are replaced by the default cutout
+///a dimension without extend (e.g. a single point) gets the size of the other or 1.0
+fn sanitize_cutout(cutout: Rect) -> Rect {
+    if !cutout.is_finite() || cutout.is_negative() {
+        return ().get_cutout(&());
+    }
+    let mut size = cutout.size();
+    if size.x <= 0.0 && size.y <= 0.0 {
+        size = GuiVec::splat(1.0);
+    } else if size.x <= 0.0 {
+        size.x = size.y;
+    } else if size.y <= 0.0 {
+        size.y = size.x;
+    }
+    Rect::from_center_size(cutout.center(), size)
+}
+
 impl Default for CanvasState {
     fn default() -> Self {
         CanvasState::new()
     }
 }
 
+///the camera of a CanvasState without the Drawables and the interaction settings
+///e.g. to persist the view of an editor between sessions (with the serde feature)
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ViewState {
+    ///[min x, min y, max x, max y] in canvas space
+    pub cutout: [f32; 4],
+    pub aspect_ratio: f32,
+    pub fill_mode: FillMode,
+    ///[min x, min y, max x, max y] in canvas space
+    pub pan_bounds: Option<[f32; 4]>,
+    pub min_cutout_size: [f32; 2],
+    ///None if unbounded
+    pub max_cutout_size: Option<[f32; 2]>,
+}
+
+fn rect_to_array(rect: Rect) -> [f32; 4] {
+    [rect.min.x, rect.min.y, rect.max.x, rect.max.y]
+}
+
+fn array_to_rect([min_x, min_y, max_x, max_y]: [f32; 4]) -> Rect {
+    Rect::from_min_max(pos2(min_x, min_y), pos2(max_x, max_y))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FillMode {
+    ///keep the aspect ratio of the cutout (scaled by the aspect_ratio of the CanvasState)
+    ///and center it in the canvas
+    #[default]
+    PreserveAspect,
+    ///stretch the cutout independently in x and y to fill the whole canvas
+    ///the aspect_ratio of the CanvasState is ignored
+    Stretch,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScrollBehavior {
+    ///scrolling zooms around the cursor
+    #[default]
+    Zoom,
+    ///scrolling pans (e.g. for trackpads)
+    ///pinching (or ctrl + scroll if the backend turns it into a zoom) zooms
+    Pan,
+    ///scrolling with ctrl (cmd on mac) held zooms and without pans
+    ///pinching zooms
+    ZoomWithCtrl,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DoubleClickAction {
+    ///reset the cutout like the Space key
+    Reset,
+    ///zoom in one mouse wheel step around the cursor
+    ZoomIn,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 enum CanvasMode {
     Dragging,
+    ///the user drags a rectangle to zoom into
+    ///contains the start of the drag in canvas space
+    BoxZoom(Pos2),
+    ///the user drags a rectangle to select
+    ///contains the start of the drag in canvas space
+    Selecting(Pos2),
     Normal,
 }
 
@@ -97,12 +991,98 @@ impl<'s, D, E: Drawable<DrawData = D>> Canvas<'s, D, E> {
         self.state.reset_cutout(self.drawable, self.draw_data)
     }
 
+    ///takes self mutably because Drawable::get_cutout does
+    pub fn data_bounds(&mut self) -> Rect {
+        self.state.data_bounds(self.drawable, self.draw_data)
+    }
+
+    pub fn fit_to_data(&mut self, margin_fraction: f32) {
+        self.state
+            .fit_to_data(self.drawable, self.draw_data, margin_fraction);
+    }
+
     pub fn center_cutout(&mut self, center: Vec2) {
         self.state.center_cutout(center);
     }
 
     pub fn set_cutout(&mut self, cutout: Rectangle) {
-        self.state.current_cutout = cutout.into();
+        self.state.set_target_cutout(cutout.into());
+    }
+
+    pub fn content_visible(&mut self, gui_space: Rect) -> bool {
+        self.state
+            .content_visible(self.drawable, self.draw_data, gui_space)
+    }
+
+    pub fn visible_fraction(&mut self, gui_space: Rect) -> f32 {
+        self.state
+            .visible_fraction(self.drawable, self.draw_data, gui_space)
+    }
+
+    pub fn export_svg(&mut self, size: Vec2) -> String {
+        self.state.export_svg(self.drawable, self.draw_data, size)
+    }
+
+    pub fn copy_svg_to_clipboard(&mut self, ctx: &Context, size: Vec2) {
+        self.state
+            .copy_svg_to_clipboard(self.drawable, self.draw_data, ctx, size);
+    }
+
+    pub fn render_to_image(&mut self, size: Vec2) -> ColorImage {
+        self.state
+            .render_to_image(self.drawable, self.draw_data, size)
+    }
+
+    pub fn mark_dirty(&mut self) {
+        self.state.mark_dirty();
+    }
+
+    pub fn draw_with_painter(&mut self, gui_space: Rect, painter: &mut dyn Painter) {
+        self.state
+            .draw_with_painter(self.drawable, self.draw_data, gui_space, painter);
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn dump_shapes_json(&mut self, gui_space: Rect) -> String {
+        self.state
+            .dump_shapes_json(self.drawable, self.draw_data, gui_space)
+    }
+
+    ///paints the cached data if the view did not change since it was cached
+    ///the data is only recorded once the view stayed the same for two frames
+    ///so panning and zooming do not clone the shapes every frame
+    fn use_static_cache(&mut self, ui: &mut Ui, gui_space: Rect) -> CacheAction {
+        let key = CacheKey {
+            cutout: self.state.current_cutout,
+            size: gui_space.size(),
+            aspect_ratio: self.state.effective_aspect_ratio(gui_space),
+            margins: self.state.margins,
+            pixels_per_point: ui.ctx().pixels_per_point(),
+            dark_mode: ui.style().visuals.dark_mode,
+        };
+
+        match &self.state.static_cache {
+            Some(cache) if cache.key == key => {
+                //the canvas may have moved in the ui since the shapes were recorded
+                let translation = gui_space.min - cache.origin;
+                for (clip_rect, shape) in &cache.shapes {
+                    let mut shape = shape.clone();
+                    shape.translate(translation);
+                    ui.painter()
+                        .with_clip_rect(clip_rect.translate(translation))
+                        .add(shape);
+                }
+                CacheAction::Replay
+            }
+            _ if self.state.last_cache_key == Some(key) => CacheAction::Record(key),
+            _ => {
+                self.state.static_cache = None;
+                self.state.last_cache_key = Some(key);
+                //record the data in the next frame if the view stays the same
+                ui.ctx().request_repaint();
+                CacheAction::Draw
+            }
+        }
     }
 
     fn manage_user_input(
@@ -111,36 +1091,41 @@ impl<'s, D, E: Drawable<DrawData = D>> Canvas<'s, D, E> {
         gui_space: Rect,
         egui_response: &mut EguiResponse,
     ) {
-        use CanvasMode::{Dragging, Normal};
+        use CanvasMode::{BoxZoom, Dragging, Normal, Selecting};
         use Key::Space;
 
         //draw curser position
-        let painter = ui.painter();
-        if let Some(curser_gui_pos) = egui_response.hover_pos() {
-            let position = Position::Gui(curser_gui_pos);
-            let curser_canvas_pos = position.to_canvas_space(
+        let draw_curser_position = self.state.repaint_on_hover || self.state.mode != Normal;
+        if let Some(curser_gui_pos) = egui_response.hover_pos().filter(|_| draw_curser_position) {
+            let aspect_ratio = self.state.effective_aspect_ratio(gui_space);
+            let mut painter = EguiPainter::new(ui, egui_response, &mut self.state.galley_cache);
+            let mut canvas_handle = CanvasHandle::new(
+                &mut painter,
+                &mut self.state.current_cutout,
                 gui_space,
-                self.state.current_cutout,
-                self.state.aspect_ratio,
+                aspect_ratio,
+                self.state.margins,
+                self.state.pick_radius_mode,
             );
-
-            let galley = painter.layout_no_wrap(
+            let curser_canvas_pos = canvas_handle
+                .convert_to_canvas_space(Position::Gui(curser_gui_pos))
+                .get_raw_pos();
+            canvas_handle.text_boxed(
+                Position::Gui(gui_space.min + GuiVec::from((10.0, 10.0))),
+                Align2::LEFT_TOP,
                 format!("Cursor: {:?}", curser_canvas_pos),
                 FontId::monospace(20.0),
                 Color32::LIGHT_GRAY,
-            );
-
-            let pos = gui_space.min + GuiVec::from((10.0, 10.0));
-
-            let size = galley.size() + GuiVec::from((10.0, 10.0));
-            painter.rect_filled(
-                Rect::from_min_size(pos, size),
-                Rounding::same(2.0),
                 Color32::DARK_BLUE,
+                5.0,
             );
-            painter.galley(pos + GuiVec::from((5.0, 5.0)), galley);
         }
+        let painter = ui.painter();
 
+        let mut keyboard_panning = false;
+        let mut gliding = false;
+        //the selection in canvas space and if it was committed in this frame
+        let mut selection = None;
         let input = ui.input();
         match self.state.mode {
             Normal => {
@@ -149,8 +1134,41 @@ impl<'s, D, E: Drawable<DrawData = D>> Canvas<'s, D, E> {
                     self.reset_cutout();
                 }
 
+                //keyboard panning
+                if self.state.keyboard_pan_enabled && egui_response.hovered() {
+                    let direction = |positive: Key, negative: Key| {
+                        input.key_down(positive) as i8 as f32
+                            - input.key_down(negative) as i8 as f32
+                    };
+                    let direction = GuiVec {
+                        x: direction(Key::ArrowRight, Key::ArrowLeft),
+                        y: direction(Key::ArrowUp, Key::ArrowDown),
+                    };
+                    if direction != GuiVec::ZERO {
+                        keyboard_panning = true;
+                        //the frame time is clamped so a slow frame does not jump
+                        let step = self.state.keyboard_pan_speed * input.unstable_dt.min(0.1);
+                        let translation = direction * self.state.current_cutout.size() * step;
+                        let new_cutout = self.state.current_cutout.translate(translation);
+                        self.state.animation = None;
+                        self.state.current_cutout = self.state.clamp_to_pan_bounds(new_cutout);
+                    }
+                }
+
+                let scroll_zooms = match self.state.scroll_behavior {
+                    ScrollBehavior::Zoom => true,
+                    ScrollBehavior::Pan => false,
+                    ScrollBehavior::ZoomWithCtrl => input.modifiers.command,
+                };
+
                 //zooming
-                if input.scroll_delta.y.abs() > 1.0 {
+                //some backends turn scrolling into horizontal scrolling while shift is held
+                let scroll = if input.modifiers.shift {
+                    input.scroll_delta.x + input.scroll_delta.y
+                } else {
+                    input.scroll_delta.y
+                };
+                if scroll_zooms && scroll.abs() > 1.0 {
                     if let Some(curser_gui_pos) = egui_response.hover_pos() {
                         //calulate the curser position in trajectory space
                         //this is the fix_point of the new cutout
@@ -160,37 +1178,159 @@ impl<'s, D, E: Drawable<DrawData = D>> Canvas<'s, D, E> {
                             .to_canvas_space(
                                 gui_space,
                                 self.state.current_cutout,
-                                self.state.aspect_ratio,
+                                self.state.effective_aspect_ratio(gui_space),
+                                self.state.margins,
                             )
                             .to_vec2();
 
                         //one click with the mouse wheel is 50.0 in scroll_delta
-                        //0.9 means that the new cutout is 90% of the old cutout
-                        let zoom_factor = 0.9_f32.powf(input.scroll_delta.y / 50.0);
-                        let inverse_zoom_factor = 1.0 - zoom_factor;
+                        let zoom_factor = self.state.zoom_step().powf(scroll / 50.0);
 
-                        //the offset is calculated so the fix_point keeps its relative position
-                        let offset = fix_point * inverse_zoom_factor
-                            + zoom_factor * self.state.current_cutout.min.to_vec2();
+                        //shift zooms only the x axis and alt only the y axis
+                        let zoom_factor = GuiVec {
+                            x: if input.modifiers.alt {
+                                1.0
+                            } else {
+                                zoom_factor
+                            },
+                            y: if input.modifiers.shift {
+                                1.0
+                            } else {
+                                zoom_factor
+                            },
+                        };
+                        self.state.zoom_around(fix_point, zoom_factor);
+                    } //else curser not on screen so ignore the scroll
+                }
 
-                        let new_cutout = Rect::from_min_size(
-                            offset.to_pos2(),
-                            self.state.current_cutout.size() * zoom_factor,
-                        );
+                //panning by scroll
+                if !scroll_zooms && egui_response.hovered() && input.scroll_delta != GuiVec::ZERO {
+                    let (_padding, scaling_factor) = Position::calculate_padding_and_scaling_factor(
+                        gui_space,
+                        self.state.current_cutout,
+                        self.state.effective_aspect_ratio(gui_space),
+                        self.state.margins,
+                    );
+                    //scrolling up shows what is above so the cutout moves up
+                    let translation = GuiVec {
+                        x: -input.scroll_delta.x / scaling_factor.x(),
+                        y: input.scroll_delta.y / scaling_factor.y(),
+                    };
+                    let new_cutout = self.state.current_cutout.translate(translation);
+                    self.state.animation = None;
+                    self.state.current_cutout = self.state.clamp_to_pan_bounds(new_cutout);
+                }
 
-                        self.state.current_cutout = new_cutout;
-                    } //else curser not on screen so ignore the scroll
+                //zooming by pinching
+                //only if scrolling does not zoom to keep the default behavior unchanged
+                let zoom_delta = input.zoom_delta();
+                if self.state.scroll_behavior != ScrollBehavior::Zoom && zoom_delta != 1.0 {
+                    if let Some(curser_gui_pos) = egui_response.hover_pos() {
+                        let fix_point = Position::Gui(curser_gui_pos)
+                            .to_canvas_space(
+                                gui_space,
+                                self.state.current_cutout,
+                                self.state.effective_aspect_ratio(gui_space),
+                                self.state.margins,
+                            )
+                            .to_vec2();
+                        //spreading the fingers zooms in so the cutout shrinks
+                        self.state
+                            .zoom_around(fix_point, GuiVec::splat(1.0 / zoom_delta));
+                    }
                 }
 
-                //drag detection
-                if egui_response.drag_started() {
+                //double click
+                //a double click must not start a drag in the same frame
+                if egui_response.double_clicked() {
+                    match self.state.double_click_action {
+                        Some(DoubleClickAction::Reset) => self.reset_cutout(),
+                        Some(DoubleClickAction::ZoomIn) => {
+                            if let Some(curser_gui_pos) = egui_response.hover_pos() {
+                                let fix_point = Position::Gui(curser_gui_pos)
+                                    .to_canvas_space(
+                                        gui_space,
+                                        self.state.current_cutout,
+                                        self.state.effective_aspect_ratio(gui_space),
+                                        self.state.margins,
+                                    )
+                                    .to_vec2();
+                                self.state
+                                    .zoom_around(fix_point, GuiVec::splat(self.state.zoom_step()));
+                            }
+                        }
+                        None => {}
+                    }
+                } else if egui_response.drag_started() {
+                    //drag detection
                     if let Some(hover_pos) = egui_response.hover_pos() {
                         if gui_space.contains(hover_pos) {
                             //drag started
-                            self.state.mode = Dragging;
+                            if input.modifiers.matches(self.state.box_zoom_modifier)
+                                && egui_response.dragged_by(PointerButton::Primary)
+                            {
+                                let start = Position::Gui(hover_pos).to_canvas_space(
+                                    gui_space,
+                                    self.state.current_cutout,
+                                    self.state.effective_aspect_ratio(gui_space),
+                                    self.state.margins,
+                                );
+                                self.state.mode = BoxZoom(start);
+                            } else if self
+                                .state
+                                .selection_modifier
+                                .is_some_and(|modifier| input.modifiers.matches(modifier))
+                                && egui_response.dragged_by(PointerButton::Primary)
+                            {
+                                let start = Position::Gui(hover_pos).to_canvas_space(
+                                    gui_space,
+                                    self.state.current_cutout,
+                                    self.state.effective_aspect_ratio(gui_space),
+                                    self.state.margins,
+                                );
+                                self.state.mode = Selecting(start);
+                            } else if egui_response.dragged_by(self.state.pan_button) {
+                                self.state.mode = Dragging;
+                            }
                         }
                     }
                 }
+
+                //pan inertia
+                //any new press, drag, scroll, pinch or keyboard panning stops the glide immediately
+                if self.state.mode != Normal
+                    || input.pointer.any_pressed()
+                    || input.scroll_delta.length() > 1.0
+                    || zoom_delta != 1.0
+                    || keyboard_panning
+                {
+                    self.state.pan_velocity = GuiVec::ZERO;
+                } else if self.state.pan_velocity != GuiVec::ZERO {
+                    //the frame time is clamped so a slow frame does not jump
+                    let dt = input.unstable_dt.min(0.1);
+                    let new_cutout = self
+                        .state
+                        .current_cutout
+                        .translate(self.state.pan_velocity * dt);
+                    self.state.current_cutout = self.state.clamp_to_pan_bounds(new_cutout);
+                    self.state.pan_velocity *= self.state.inertia_decay_per_second.powf(dt);
+
+                    let (_padding, scaling_factor) = Position::calculate_padding_and_scaling_factor(
+                        gui_space,
+                        self.state.current_cutout,
+                        self.state.effective_aspect_ratio(gui_space),
+                        self.state.margins,
+                    );
+                    let gui_velocity = GuiVec {
+                        x: self.state.pan_velocity.x * scaling_factor.x(),
+                        y: self.state.pan_velocity.y * scaling_factor.y(),
+                    };
+                    if gui_velocity.length() < self.state.inertia_min_velocity {
+                        self.state.pan_velocity = GuiVec::ZERO;
+                    } else {
+                        gliding = true;
+                    }
+                }
             }
 
             Dragging => {
@@ -201,9 +1341,17 @@ impl<'s, D, E: Drawable<DrawData = D>> Canvas<'s, D, E> {
                     let (_padding, scaling_factor) = Position::calculate_padding_and_scaling_factor(
                         gui_space,
                         self.state.current_cutout,
-                        self.state.aspect_ratio,
+                        self.state.effective_aspect_ratio(gui_space),
+                        self.state.margins,
                     );
-                    let translation_raw = egui_response.drag_delta();
+                    let mut translation_raw = egui_response.drag_delta();
+                    let constrained = self
+                        .state
+                        .constrain_pan_modifier
+                        .is_some_and(|modifier| input.modifiers.matches(modifier));
+                    if constrained {
+                        translation_raw = lock_to_dominant_axis(translation_raw);
+                    }
                     let translation_scaled = GuiVec {
                         x: translation_raw.x / scaling_factor.x(),
                         y: translation_raw.y / scaling_factor.y(),
@@ -213,19 +1361,167 @@ impl<'s, D, E: Drawable<DrawData = D>> Canvas<'s, D, E> {
                         y: translation_scaled.y,
                     };
                     let new_cutout = self.state.current_cutout.translate(translation_rotated);
-                    self.state.current_cutout = new_cutout;
+                    //remember the velocity for the glide after the release
+                    //only if there is a glide so the view does not jump after the release
+                    if self.state.pan_inertia && input.unstable_dt > 0.0 {
+                        self.state.pan_velocity = translation_rotated / input.unstable_dt;
+                    }
+                    self.state.animation = None;
+                    self.state.current_cutout = self.state.clamp_to_pan_bounds(new_cutout);
+                }
+            }
+
+            BoxZoom(start) => {
+                let end = input.pointer.interact_pos().map(|end_gui_pos| {
+                    Position::Gui(end_gui_pos).to_canvas_space(
+                        gui_space,
+                        self.state.current_cutout,
+                        self.state.effective_aspect_ratio(gui_space),
+                        self.state.margins,
+                    )
+                });
+
+                if egui_response.drag_released() {
+                    self.state.mode = Normal;
+                    if let Some(end) = end {
+                        //from_two_pos normalizes the corners
+                        let mut new_cutout = Rect::from_two_pos(start, end);
+                        if self.state.box_zoom_match_aspect {
+                            new_cutout = self.state.expand_to_view_aspect(new_cutout, gui_space);
+                        }
+
+                        //ignore degenerated rectangles and zooms that would cross a zoom limit
+                        if new_cutout.width() > 0.0
+                            && new_cutout.height() > 0.0
+                            && self.state.cutout_size_allowed(new_cutout)
+                        {
+                            self.state
+                                .set_target_cutout(self.state.clamp_to_pan_bounds(new_cutout));
+                        }
+                    }
+                } else if let Some(end) = end {
+                    //draw the rectangle that will be zoomed into
+                    let corner_a = Position::Canvas(start).to_gui_space(
+                        gui_space,
+                        self.state.current_cutout,
+                        self.state.effective_aspect_ratio(gui_space),
+                        self.state.margins,
+                    );
+                    let corner_b = Position::Canvas(end).to_gui_space(
+                        gui_space,
+                        self.state.current_cutout,
+                        self.state.effective_aspect_ratio(gui_space),
+                        self.state.margins,
+                    );
+                    painter.rect(
+                        Rect::from_two_pos(corner_a, corner_b),
+                        Rounding::none(),
+                        Color32::from_rgba_unmultiplied(100, 150, 255, 40),
+                        (1.0, Color32::LIGHT_BLUE),
+                    );
+                }
+            }
+
+            Selecting(start) => {
+                let end = input.pointer.interact_pos().map(|end_gui_pos| {
+                    Position::Gui(end_gui_pos).to_canvas_space(
+                        gui_space,
+                        self.state.current_cutout,
+                        self.state.effective_aspect_ratio(gui_space),
+                        self.state.margins,
+                    )
+                });
+
+                if egui_response.drag_released() {
+                    self.state.mode = Normal;
+                    //the selection is delivered once more so the Drawables can act on it
+                    selection = end.map(|end| (Rect::from_two_pos(start, end), true));
+                } else if let Some(end) = end {
+                    selection = Some((Rect::from_two_pos(start, end), false));
+
+                    //draw the selected rectangle
+                    let corner_a = Position::Canvas(start).to_gui_space(
+                        gui_space,
+                        self.state.current_cutout,
+                        self.state.effective_aspect_ratio(gui_space),
+                        self.state.margins,
+                    );
+                    let corner_b = Position::Canvas(end).to_gui_space(
+                        gui_space,
+                        self.state.current_cutout,
+                        self.state.effective_aspect_ratio(gui_space),
+                        self.state.margins,
+                    );
+                    painter.rect(
+                        Rect::from_two_pos(corner_a, corner_b),
+                        Rounding::none(),
+                        Color32::from_rgba_unmultiplied(150, 150, 150, 40),
+                        (1.0, Color32::GRAY),
+                    );
                 }
             }
         }
         drop(input);
 
-        let response = Response::from(&*egui_response);
+        //the icon is only set in this frame so egui resets it after the drag
+        let cursor_icon = match self.state.mode {
+            Dragging => self.state.drag_cursor_icon,
+            Normal if egui_response.hovered() && self.state.sense.drag => {
+                self.state.hover_cursor_icon
+            }
+            _ => None,
+        };
+        if let Some(cursor_icon) = cursor_icon {
+            ui.output().cursor_icon = cursor_icon;
+        }
+
+        //keep panning smoothly while the keys are held or the cutout glides
+        if keyboard_panning || gliding {
+            ui.ctx().request_repaint();
+        }
+
+        let mut response = Response::from(&*egui_response);
+        response.canvas_pos = response.curser_pos.map(|curser_pos| {
+            Position::Canvas(curser_pos.to_canvas_space(
+                gui_space,
+                self.state.current_cutout,
+                self.state.effective_aspect_ratio(gui_space),
+                self.state.margins,
+            ))
+        });
+        if response.dragged {
+            let (_padding, scaling_factor) = Position::calculate_padding_and_scaling_factor(
+                gui_space,
+                self.state.current_cutout,
+                self.state.effective_aspect_ratio(gui_space),
+                self.state.margins,
+            );
+            let drag_delta = egui_response.drag_delta();
+            //the y axis of the canvas points up
+            response.drag_delta_canvas = Vec2::new(
+                drag_delta.x / scaling_factor.x(),
+                -drag_delta.y / scaling_factor.y(),
+            );
+        }
+        if egui_response.hovered() {
+            response.scroll_delta = ui.input().scroll_delta.y;
+        }
+        if response.secondary_clicked {
+            response.secondary_click_pos = response.canvas_pos;
+        }
+        if let Some((selection, committed)) = selection {
+            response.selection = Some(selection);
+            response.selection_committed = committed;
+        }
+        let aspect_ratio = self.state.effective_aspect_ratio(gui_space);
+        let mut painter = EguiPainter::new(ui, egui_response, &mut self.state.galley_cache);
         let canvas_handle = CanvasHandle::new(
-            ui,
-            egui_response,
+            &mut painter,
             &mut self.state.current_cutout,
             gui_space,
-            self.state.aspect_ratio,
+            aspect_ratio,
+            self.state.margins,
+            self.state.pick_radius_mode,
         );
 
         //pass through
@@ -235,19 +1531,65 @@ impl<'s, D, E: Drawable<DrawData = D>> Canvas<'s, D, E> {
 
 impl<'s, D, E: Drawable<DrawData = D>> Widget for Canvas<'s, D, E> {
     fn ui(mut self, ui: &mut Ui) -> EguiResponse {
-        let mut response = ui.allocate_response(vec2(50.0, 50.0), Sense::click_and_drag());
+        let mut response = ui.allocate_response(vec2(50.0, 50.0), self.state.sense);
         let gui_space = response.rect;
         ui.set_clip_rect(gui_space);
 
+        self.state.time = ui.input().time;
+        if self.state.animate() {
+            ui.ctx().request_repaint();
+        }
+        self.state
+            .galley_cache
+            .new_frame(ui.ctx().pixels_per_point());
+
         //draw the Drawable Data
+        let aspect_ratio = self.state.effective_aspect_ratio(gui_space);
+        let cache_action = if self.state.cache_static {
+            self.use_static_cache(ui, gui_space)
+        } else {
+            CacheAction::Draw
+        };
+        let draw_range = self.state.draw_range.clone();
+        let mut painter = EguiPainter::new(ui, &mut response, &mut self.state.galley_cache);
+        if let CacheAction::Record(_) = cache_action {
+            painter.start_recording();
+        }
+        let mut canvas_handle = CanvasHandle::new(
+            &mut painter,
+            &mut self.state.current_cutout,
+            gui_space,
+            aspect_ratio,
+            self.state.margins,
+            self.state.pick_radius_mode,
+        );
+        if cache_action != CacheAction::Replay {
+            draw_in_range(
+                self.drawable,
+                &mut canvas_handle,
+                self.draw_data,
+                draw_range,
+            );
+        }
+        drop(canvas_handle);
+        if let (CacheAction::Record(key), Some(shapes)) = (cache_action, painter.stop_recording()) {
+            self.state.static_cache = Some(StaticCache {
+                key,
+                origin: gui_space.min,
+                shapes,
+            });
+        }
+
         let mut canvas_handle = CanvasHandle::new(
-            ui,
-            &mut response,
+            &mut painter,
             &mut self.state.current_cutout,
             gui_space,
-            self.state.aspect_ratio,
+            aspect_ratio,
+            self.state.margins,
+            self.state.pick_radius_mode,
         );
-        self.drawable.draw(&mut canvas_handle, self.draw_data);
+        self.drawable
+            .draw_overlay(&mut canvas_handle, self.draw_data);
 
         //manage user input
         self.manage_user_input(ui, gui_space, &mut response);
@@ -255,9 +1597,62 @@ impl<'s, D, E: Drawable<DrawData = D>> Widget for Canvas<'s, D, E> {
         if self.state.draw_frame {
             //draw a frame around the Trajectories
             let painter = ui.painter();
-            painter.rect_stroke(gui_space, 0.0, (5.0, Color32::DARK_RED));
+            painter.rect_stroke(gui_space, 0.0, self.state.frame_stroke);
         }
 
         response
     }
 }
+
+///locks a drag to its dominant axis for the constrained panning
+fn lock_to_dominant_axis(drag_delta: GuiVec) -> GuiVec {
+    if drag_delta.x.abs() >= drag_delta.y.abs() {
+        GuiVec::new(drag_delta.x, 0.0)
+    } else {
+        GuiVec::new(0.0, drag_delta.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mostly_horizontal_drag_is_locked_to_the_x_axis() {
+        let locked = lock_to_dominant_axis(GuiVec::new(12.0, -3.0));
+        assert_eq!(locked, GuiVec::new(12.0, 0.0));
+    }
+
+    #[test]
+    fn mostly_vertical_drag_is_locked_to_the_y_axis() {
+        let locked = lock_to_dominant_axis(GuiVec::new(-2.0, 9.0));
+        assert_eq!(locked, GuiVec::new(0.0, 9.0));
+    }
+
+    #[test]
+    fn empty_data_gets_the_default_cutout() {
+        assert_eq!(sanitize_cutout(Rect::NOTHING), ().get_cutout(&()));
+    }
+
+    #[test]
+    fn single_point_gets_a_unit_cutout_around_it() {
+        let point = Pos2::new(3.0, -2.0);
+        let cutout = sanitize_cutout(Rect::from_min_max(point, point));
+        assert_eq!(cutout, Rect::from_center_size(point, GuiVec::splat(1.0)));
+    }
+
+    #[test]
+    fn flat_data_gets_a_square_cutout() {
+        let cutout = sanitize_cutout(Rect::from_min_max(Pos2::new(0.0, 1.0), Pos2::new(4.0, 1.0)));
+        assert_eq!(cutout.size(), GuiVec::splat(4.0));
+        assert_eq!(cutout.center(), Pos2::new(2.0, 1.0));
+    }
+
+    #[test]
+    fn infinite_coordinates_get_the_default_cutout() {
+        let infinite = Rect::from_min_max(Pos2::new(0.0, 0.0), Pos2::new(f32::INFINITY, 1.0));
+        assert_eq!(sanitize_cutout(infinite), ().get_cutout(&()));
+        let nan = Rect::from_min_max(Pos2::new(f32::NAN, 0.0), Pos2::new(1.0, 1.0));
+        assert_eq!(sanitize_cutout(nan), ().get_cutout(&()));
+    }
+}
@@ -0,0 +1,366 @@
+use std::cell::RefCell;
+
+use eframe::egui::Vec2 as GuiVec;
+use eframe::egui::{Color32, Rect, Response as EguiResponse, Stroke, Ui};
+use eframe::emath::{Align2, Pos2};
+use eframe::epaint::{FontId, Mesh, RectShape, Rounding, Shape};
+
+use crate::galley_cache::GalleyCache;
+
+///without egui there are no font metrics
+///so the width of a char is estimated as this fraction of the font size
+const CHAR_WIDTH_FACTOR: f32 = 0.6;
+
+///the backend the CanvasHandle paints with
+///everything is in gui space
+///
+///the CanvasHandle holds the painter as a trait object
+///so Drawables can be run against any painter without changing the Drawable trait
+pub trait Painter {
+    fn line_segment(&mut self, points: [Pos2; 2], stroke: Stroke);
+
+    ///an open path through the points
+    ///the default paints one line_segment per segment so the joins are not mitered
+    fn polyline(&mut self, points: Vec<Pos2>, stroke: Stroke) {
+        for segment in points.windows(2) {
+            self.line_segment([segment[0], segment[1]], stroke);
+        }
+    }
+
+    fn circle_filled(&mut self, center: Pos2, radius: f32, fill_color: Color32);
+
+    fn rect(&mut self, rect: Rect, rounding: Rounding, fill_color: Color32, stroke: Stroke);
+
+    ///the polygon is closed and has at least 3 points
+    ///the default fills it as a triangle fan with mesh (so only convex polygons are filled right)
+    ///and strokes it with line_segment
+    fn polygon(&mut self, points: Vec<Pos2>, fill_color: Color32, stroke: Stroke) {
+        if points.len() < 3 {
+            return;
+        }
+        if fill_color != Color32::TRANSPARENT {
+            let mut mesh = Mesh::default();
+            for point in &points {
+                mesh.colored_vertex(*point, fill_color);
+            }
+            for index in 1..points.len() as u32 - 1 {
+                mesh.add_triangle(0, index, index + 1);
+            }
+            self.mesh(mesh);
+        }
+        if stroke.width > 0.0 && stroke.color != Color32::TRANSPARENT {
+            for (index, point) in points.iter().enumerate() {
+                let next = points[(index + 1) % points.len()];
+                self.line_segment([*point, next], stroke);
+            }
+        }
+    }
+
+    ///triangles with a color per vertex
+    ///the vertices use the white uv of the font texture (e.g. by Mesh::colored_vertex)
+    ///painters without triangles skip it (the default)
+    #[allow(unused_variables)]
+    fn mesh(&mut self, mesh: Mesh) {}
+
+    fn text(
+        &mut self,
+        pos: Pos2,
+        anchor: Align2,
+        text: String,
+        font_id: FontId,
+        text_color: Color32,
+    );
+
+    fn text_size(&self, text: String, font_id: FontId) -> GuiVec;
+
+    ///paints the text and returns its size
+    ///painters that lay out the text should do it only once
+    fn text_galley(
+        &mut self,
+        pos: Pos2,
+        anchor: Align2,
+        text: String,
+        font_id: FontId,
+        text_color: Color32,
+    ) -> GuiVec {
+        let size = self.text_size(text.clone(), font_id.clone());
+        self.text(pos, anchor, text, font_id, text_color);
+        size
+    }
+
+    ///paints an egui shape that has no counterpart in the other methods (e.g. a textured mesh)
+    ///painters that do not paint into egui skip it
+    #[allow(unused_variables)]
+    fn shape(&mut self, shape: Shape) {}
+
+    fn clip_rect(&self) -> Rect;
+
+    fn set_clip_rect(&mut self, clip_rect: Rect);
+
+    ///the ui the painter paints into
+    ///None if the painter does not paint into egui (e.g. for exporting)
+    fn ui(&self) -> Option<&Ui> {
+        None
+    }
+
+    fn ui_mut(&mut self) -> Option<&mut Ui> {
+        None
+    }
+
+    ///false if the painter does not know the theme (e.g. for exporting)
+    fn dark_mode(&self) -> bool {
+        self.ui().is_some_and(|ui| ui.style().visuals.dark_mode)
+    }
+
+    ///the response of the canvas the painter paints into
+    ///None if the painter does not paint into egui (e.g. for exporting)
+    fn response_mut(&mut self) -> Option<&mut EguiResponse> {
+        None
+    }
+}
+
+///estimates the size of the text for painters without font metrics
+pub(crate) fn estimate_text_size(text: &str, font_id: &FontId) -> GuiVec {
+    let num_chars = text.chars().count() as f32;
+    GuiVec {
+        x: num_chars * font_id.size * CHAR_WIDTH_FACTOR,
+        y: font_id.size,
+    }
+}
+
+///the average of the colors for painters without per vertex colors
+pub(crate) fn average_color(colors: impl IntoIterator<Item = Color32>) -> Color32 {
+    let mut sum = [0_u32; 4];
+    let mut num_colors = 0;
+    for color in colors {
+        for (sum, channel) in sum.iter_mut().zip(color.to_array()) {
+            *sum += channel as u32;
+        }
+        num_colors += 1;
+    }
+    if num_colors == 0 {
+        return Color32::TRANSPARENT;
+    }
+    let [r, g, b, a] = sum.map(|sum| (sum / num_colors) as u8);
+    Color32::from_rgba_premultiplied(r, g, b, a)
+}
+
+///paints into the egui Ui of the canvas
+pub(crate) struct EguiPainter<'p> {
+    ui: &'p mut Ui,
+    response: &'p mut EguiResponse,
+    ///RefCell because text_size only gets &self
+    galley_cache: RefCell<&'p mut GalleyCache>,
+    ///the painted shapes with their clip rect while recording
+    recording: Option<Vec<(Rect, Shape)>>,
+}
+
+impl<'p> EguiPainter<'p> {
+    pub(crate) fn new(
+        ui: &'p mut Ui,
+        response: &'p mut EguiResponse,
+        galley_cache: &'p mut GalleyCache,
+    ) -> EguiPainter<'p> {
+        EguiPainter {
+            ui,
+            response,
+            galley_cache: RefCell::new(galley_cache),
+            recording: None,
+        }
+    }
+
+    ///everything painted from now on is also recorded (e.g. to cache it)
+    pub(crate) fn start_recording(&mut self) {
+        self.recording = Some(Vec::new());
+    }
+
+    ///the shapes painted since start_recording
+    pub(crate) fn stop_recording(&mut self) -> Option<Vec<(Rect, Shape)>> {
+        self.recording.take()
+    }
+
+    fn add(&mut self, shape: Shape) {
+        if let Some(recording) = &mut self.recording {
+            recording.push((self.ui.clip_rect(), shape.clone()));
+        }
+        self.ui.painter().add(shape);
+    }
+}
+
+impl<'p> Painter for EguiPainter<'p> {
+    fn line_segment(&mut self, points: [Pos2; 2], stroke: Stroke) {
+        self.add(Shape::line_segment(points, stroke));
+    }
+
+    fn polyline(&mut self, points: Vec<Pos2>, stroke: Stroke) {
+        self.add(Shape::line(points, stroke));
+    }
+
+    fn circle_filled(&mut self, center: Pos2, radius: f32, fill_color: Color32) {
+        self.add(Shape::circle_filled(center, radius, fill_color));
+    }
+
+    fn rect(&mut self, rect: Rect, rounding: Rounding, fill_color: Color32, stroke: Stroke) {
+        self.add(Shape::Rect(RectShape {
+            rect,
+            rounding,
+            fill: fill_color,
+            stroke,
+        }));
+    }
+
+    fn polygon(&mut self, points: Vec<Pos2>, fill_color: Color32, stroke: Stroke) {
+        self.add(Shape::convex_polygon(points, fill_color, stroke));
+    }
+
+    fn mesh(&mut self, mesh: Mesh) {
+        self.add(Shape::mesh(mesh));
+    }
+
+    fn text(
+        &mut self,
+        pos: Pos2,
+        anchor: Align2,
+        text: String,
+        font_id: FontId,
+        text_color: Color32,
+    ) {
+        self.text_galley(pos, anchor, text, font_id, text_color);
+    }
+
+    fn text_size(&self, text: String, font_id: FontId) -> GuiVec {
+        let gally = self
+            .galley_cache
+            .borrow_mut()
+            .layout(self.ui, text, font_id);
+        gally.size()
+    }
+
+    fn text_galley(
+        &mut self,
+        pos: Pos2,
+        anchor: Align2,
+        text: String,
+        font_id: FontId,
+        text_color: Color32,
+    ) -> GuiVec {
+        let gally = self
+            .galley_cache
+            .borrow_mut()
+            .layout(self.ui, text, font_id);
+        let size = gally.size();
+        let rect = anchor.anchor_rect(Rect::from_min_size(pos, size));
+        if !gally.is_empty() {
+            self.add(Shape::galley_with_color(rect.min, gally, text_color));
+        }
+        size
+    }
+
+    fn shape(&mut self, shape: Shape) {
+        self.add(shape);
+    }
+
+    fn clip_rect(&self) -> Rect {
+        self.ui.clip_rect()
+    }
+
+    fn set_clip_rect(&mut self, clip_rect: Rect) {
+        self.ui.set_clip_rect(clip_rect);
+    }
+
+    fn ui(&self) -> Option<&Ui> {
+        Some(self.ui)
+    }
+
+    fn ui_mut(&mut self) -> Option<&mut Ui> {
+        Some(self.ui)
+    }
+
+    fn response_mut(&mut self) -> Option<&mut EguiResponse> {
+        Some(self.response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use eframe::emath::pos2;
+
+    use super::*;
+
+    ///implements only the methods without a default
+    #[derive(Default)]
+    struct MinimalPainter {
+        line_segments: Vec<[Pos2; 2]>,
+        triangles: usize,
+    }
+
+    impl Painter for MinimalPainter {
+        fn line_segment(&mut self, points: [Pos2; 2], _stroke: Stroke) {
+            self.line_segments.push(points);
+        }
+
+        fn circle_filled(&mut self, _center: Pos2, _radius: f32, _fill_color: Color32) {}
+
+        fn rect(
+            &mut self,
+            _rect: Rect,
+            _rounding: Rounding,
+            _fill_color: Color32,
+            _stroke: Stroke,
+        ) {
+        }
+
+        fn mesh(&mut self, mesh: Mesh) {
+            self.triangles += mesh.indices.len() / 3;
+        }
+
+        fn text(
+            &mut self,
+            _pos: Pos2,
+            _anchor: Align2,
+            _text: String,
+            _font_id: FontId,
+            _text_color: Color32,
+        ) {
+        }
+
+        fn text_size(&self, text: String, font_id: FontId) -> GuiVec {
+            estimate_text_size(&text, &font_id)
+        }
+
+        fn clip_rect(&self) -> Rect {
+            Rect::EVERYTHING
+        }
+
+        fn set_clip_rect(&mut self, _clip_rect: Rect) {}
+    }
+
+    #[test]
+    fn default_polyline_paints_every_segment() {
+        let mut painter = MinimalPainter::default();
+        let points = vec![pos2(0.0, 0.0), pos2(1.0, 0.0), pos2(1.0, 1.0)];
+        painter.polyline(points, Stroke::new(1.0, Color32::RED));
+        assert_eq!(
+            painter.line_segments,
+            [
+                [pos2(0.0, 0.0), pos2(1.0, 0.0)],
+                [pos2(1.0, 0.0), pos2(1.0, 1.0)]
+            ]
+        );
+    }
+
+    #[test]
+    fn default_polygon_is_filled_with_a_mesh_and_closed() {
+        let mut painter = MinimalPainter::default();
+        let square = vec![
+            pos2(0.0, 0.0),
+            pos2(1.0, 0.0),
+            pos2(1.0, 1.0),
+            pos2(0.0, 1.0),
+        ];
+        painter.polygon(square, Color32::RED, Stroke::new(1.0, Color32::BLACK));
+        assert_eq!(painter.triangles, 2);
+        assert_eq!(painter.line_segments.len(), 4);
+        assert_eq!(painter.line_segments[3], [pos2(0.0, 1.0), pos2(0.0, 0.0)]);
+    }
+}
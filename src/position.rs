@@ -1,9 +1,46 @@
-const MIN_PADDING: f32 = 20.0;
+///the default of the smallest space between the drawn cutout and the border of the canvas
+const DEFAULT_MIN_PADDING: f32 = 20.0;
 
 use eframe::egui::Vec2 as GuiVec;
-use eframe::egui::{Pos2, Rect};
+use eframe::egui::{pos2, Pos2, Rect};
 use simple_math::Vec2;
 
+///the smallest space between the drawn cutout and each border of the canvas in gui pixels
+///e.g. more on the left and bottom to make room for tick labels
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Margins {
+    pub left: f32,
+    pub right: f32,
+    pub top: f32,
+    pub bottom: f32,
+}
+
+impl Margins {
+    pub fn same(margin: f32) -> Margins {
+        Margins {
+            left: margin,
+            right: margin,
+            top: margin,
+            bottom: margin,
+        }
+    }
+
+    ///the part of gui_space inside of the margins
+    fn content_rect(self, gui_space: Rect) -> Rect {
+        Rect::from_min_max(
+            pos2(gui_space.min.x + self.left, gui_space.min.y + self.top),
+            pos2(gui_space.max.x - self.right, gui_space.max.y - self.bottom),
+        )
+    }
+}
+
+impl Default for Margins {
+    ///20.0 on every side
+    fn default() -> Self {
+        Margins::same(DEFAULT_MIN_PADDING)
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum Position {
     Gui(Pos2),
@@ -23,19 +60,21 @@ impl Position {
         gui_space: Rect,
         current_cutout: Rect,
         aspect_ratio: f32,
+        margins: Margins,
     ) -> Pos2 {
         use Position::{Canvas, Gui, Overlay};
         match self {
             Canvas(_) => {
-                let overlay =
-                    Overlay(self.to_overlay_space(gui_space, current_cutout, aspect_ratio));
-                overlay.to_gui_space(gui_space, current_cutout, aspect_ratio)
+                let overlay = Overlay(self.to_overlay_space(
+                    gui_space,
+                    current_cutout,
+                    aspect_ratio,
+                    margins,
+                ));
+                overlay.to_gui_space(gui_space, current_cutout, aspect_ratio, margins)
             }
 
-            Overlay(pos) => Pos2 {
-                x: pos.x,
-                y: gui_space.max.y - pos.y + gui_space.min.y,
-            },
+            Overlay(pos) => Position::flip_y(pos, gui_space),
 
             Gui(pos) => pos,
         }
@@ -46,10 +85,15 @@ impl Position {
         gui_space: Rect,
         current_cutout: Rect,
         aspect_ratio: f32,
+        margins: Margins,
     ) -> Pos2 {
         use Position::{Canvas, Gui, Overlay};
-        let (padding, scaling_factor) =
-            Position::calculate_padding_and_scaling_factor(gui_space, current_cutout, aspect_ratio);
+        let (padding, scaling_factor) = Position::calculate_padding_and_scaling_factor(
+            gui_space,
+            current_cutout,
+            aspect_ratio,
+            margins,
+        );
         match self {
             Canvas(pos) => {
                 let padding: GuiVec = padding.into();
@@ -63,10 +107,7 @@ impl Position {
             }
             Overlay(pos) => pos,
 
-            Gui(pos) => Pos2 {
-                x: pos.x,
-                y: gui_space.max.y - pos.y + gui_space.min.y,
-            },
+            Gui(pos) => Position::flip_y(pos, gui_space),
         }
     }
 
@@ -75,40 +116,84 @@ impl Position {
         gui_space: Rect,
         current_cutout: Rect,
         aspect_ratio: f32,
+        margins: Margins,
     ) -> Pos2 {
         use Position::{Canvas, Gui, Overlay};
-        let (padding, scaling_factor) =
-            Position::calculate_padding_and_scaling_factor(gui_space, current_cutout, aspect_ratio);
+        let (padding, scaling_factor) = Position::calculate_padding_and_scaling_factor(
+            gui_space,
+            current_cutout,
+            aspect_ratio,
+            margins,
+        );
         match self {
             Canvas(pos) => pos,
 
-            Overlay(pos) => {
-                let padding: GuiVec = padding.into();
-                let overlay_vec_moved = pos.to_vec2() - padding - gui_space.min.to_vec2();
-                let overlay_vec_scaled = GuiVec {
-                    x: overlay_vec_moved.x / scaling_factor.x(),
-                    y: overlay_vec_moved.y / scaling_factor.y(),
-                };
-                let canvas_vec = overlay_vec_scaled + current_cutout.min.to_vec2();
-                canvas_vec.to_pos2()
-            }
+            Overlay(pos) => Position::overlay_to_canvas(
+                pos,
+                gui_space,
+                current_cutout,
+                (padding, scaling_factor),
+            ),
 
-            Gui(_) => {
-                let overlay =
-                    Overlay(self.to_overlay_space(gui_space, current_cutout, aspect_ratio));
-                overlay.to_canvas_space(gui_space, current_cutout, aspect_ratio)
-            }
+            Gui(pos) => Position::overlay_to_canvas(
+                Position::flip_y(pos, gui_space),
+                gui_space,
+                current_cutout,
+                (padding, scaling_factor),
+            ),
+        }
+    }
+
+    ///converts between gui and overlay space (the conversion is its own inverse)
+    pub(crate) fn flip_y(pos: Pos2, gui_space: Rect) -> Pos2 {
+        Pos2 {
+            x: pos.x,
+            y: gui_space.max.y - pos.y + gui_space.min.y,
         }
     }
 
+    ///padding_and_scaling is the result of calculate_padding_and_scaling_factor
+    ///so callers that convert several positions only calculate it once
+    pub(crate) fn overlay_to_canvas(
+        pos: Pos2,
+        gui_space: Rect,
+        current_cutout: Rect,
+        padding_and_scaling: (Vec2, Vec2),
+    ) -> Pos2 {
+        let (padding, scaling_factor) = padding_and_scaling;
+        let padding: GuiVec = padding.into();
+        let overlay_vec_moved = pos.to_vec2() - padding - gui_space.min.to_vec2();
+        let overlay_vec_scaled = GuiVec {
+            x: overlay_vec_moved.x / scaling_factor.x(),
+            y: overlay_vec_moved.y / scaling_factor.y(),
+        };
+        let canvas_vec = overlay_vec_scaled + current_cutout.min.to_vec2();
+        canvas_vec.to_pos2()
+    }
+
+    ///the aspect ratio that stretches the current cutout to fill the whole gui_space
+    pub(super) fn calculate_stretch_aspect_ratio(
+        gui_space: Rect,
+        current_cutout: Rect,
+        margins: Margins,
+    ) -> f32 {
+        margins.content_rect(gui_space).aspect_ratio() / current_cutout.aspect_ratio()
+    }
+
+    ///the padding (in gui pixels) between the gui_space and the drawn cutout
+    ///and the gui pixels per canvas unit in x and y direction
+    ///the padding uses the stretched size so the cutout stays centered for every aspect ratio
+    ///every conversion between the spaces goes through here
     pub(super) fn calculate_padding_and_scaling_factor(
         gui_space: Rect,
         current_cutout: Rect,
         aspect_ratio: f32,
+        margins: Margins,
     ) -> (Vec2, Vec2) {
         //calulate the rations of the spaces
         let ratio_trajectories = current_cutout.aspect_ratio() * aspect_ratio;
-        let ratio_canvas = gui_space.shrink(MIN_PADDING).aspect_ratio();
+        let content_rect = margins.content_rect(gui_space);
+        let ratio_canvas = content_rect.aspect_ratio();
 
         let (x_stretch, y_stretch) = if aspect_ratio > 1.0 {
             (aspect_ratio, 1.0)
@@ -122,19 +207,21 @@ impl Position {
         let y_padding;
         if ratio_trajectories < ratio_canvas {
             // y-Axe is limiting
-            scaling_factor =
-                gui_space.shrink(MIN_PADDING).height() / (current_cutout.height() * y_stretch);
-            x_padding =
-                (gui_space.width() - current_cutout.width() * scaling_factor * x_stretch) / 2.0;
-            y_padding = MIN_PADDING;
+            scaling_factor = content_rect.height() / (current_cutout.height() * y_stretch);
+            x_padding = margins.left
+                + (content_rect.width() - current_cutout.width() * scaling_factor * x_stretch)
+                    / 2.0;
+            y_padding = margins.bottom;
         } else {
             // x-Axe is limiting
-            scaling_factor =
-                gui_space.shrink(MIN_PADDING).width() / (current_cutout.width() * x_stretch);
-            x_padding = MIN_PADDING;
-            y_padding =
-                (gui_space.height() - current_cutout.height() * scaling_factor * y_stretch) / 2.0;
+            scaling_factor = content_rect.width() / (current_cutout.width() * x_stretch);
+            x_padding = margins.left;
+            y_padding = margins.bottom
+                + (content_rect.height() - current_cutout.height() * scaling_factor * y_stretch)
+                    / 2.0;
         }
+        //the padding above already includes the stretch
+        //so the cutout ends exactly at the opposite padding in both branches
         let x_scaling_factor = scaling_factor * x_stretch;
         let y_scaling_factor = scaling_factor * y_stretch;
 
@@ -145,3 +232,74 @@ impl Position {
         (padding, scaling_factor)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_round_trip(gui_space: Rect, current_cutout: Rect, aspect_ratio: f32) {
+        let margins = Margins::default();
+        for (x, y) in [(0.0, 0.0), (0.3, 0.8), (0.5, 0.5), (1.0, 0.1), (1.0, 1.0)] {
+            let gui_pos = gui_space.lerp(GuiVec::new(x, y));
+            let canvas_pos = Position::Gui(gui_pos).to_canvas_space(
+                gui_space,
+                current_cutout,
+                aspect_ratio,
+                margins,
+            );
+            let round_trip = Position::Canvas(canvas_pos).to_gui_space(
+                gui_space,
+                current_cutout,
+                aspect_ratio,
+                margins,
+            );
+            assert!(
+                (round_trip - gui_pos).length() < 1e-3,
+                "{gui_pos:?} came back as {round_trip:?} for the aspect ratio {aspect_ratio}"
+            );
+        }
+    }
+
+    #[test]
+    fn gui_canvas_gui_round_trip_for_every_aspect_ratio() {
+        let wide = Rect::from_min_max(pos2(10.0, 20.0), pos2(810.0, 320.0));
+        let tall = Rect::from_min_max(pos2(-5.0, 0.0), pos2(195.0, 600.0));
+        let cutout = Rect::from_min_max(pos2(-3.0, 2.0), pos2(7.0, 4.5));
+        for gui_space in [wide, tall] {
+            for aspect_ratio in [0.1, 0.5, 1.0, 2.0, 10.0] {
+                assert_round_trip(gui_space, cutout, aspect_ratio);
+            }
+            let stretch =
+                Position::calculate_stretch_aspect_ratio(gui_space, cutout, Margins::default());
+            assert_round_trip(gui_space, cutout, stretch);
+        }
+    }
+
+    #[test]
+    fn padding_and_scaling_factor_of_known_aspect_ratios() {
+        //400 x 200 pixels inside of the default margins
+        let gui_space = Rect::from_min_max(pos2(0.0, 0.0), pos2(440.0, 240.0));
+        let cutout = Rect::from_min_max(pos2(0.0, 0.0), pos2(10.0, 10.0));
+
+        //(aspect ratio, padding, scaling factor)
+        let expected = [
+            (1.0, (120.0, 20.0), (20.0, 20.0)),
+            (2.0, (20.0, 20.0), (40.0, 20.0)),
+            (0.5, (170.0, 20.0), (10.0, 20.0)),
+        ];
+        for (aspect_ratio, padding, scaling_factor) in expected {
+            let (actual_padding, actual_scaling_factor) =
+                Position::calculate_padding_and_scaling_factor(
+                    gui_space,
+                    cutout,
+                    aspect_ratio,
+                    Margins::default(),
+                );
+            assert_eq!((actual_padding.x(), actual_padding.y()), padding);
+            assert_eq!(
+                (actual_scaling_factor.x(), actual_scaling_factor.y()),
+                scaling_factor
+            );
+        }
+    }
+}
@@ -0,0 +1,249 @@
+//!a small cpu rasterizer for CanvasState::render_to_image
+//!
+//!egui 0.18 paints in the backend after the frame and has no way to read the pixels back
+//!so the shapes are tessellated with epaint and the triangles are filled here
+//!it only covers what the Painter trait paints (no textures besides the font atlas)
+//!and is not used to paint the widget itself
+
+use eframe::egui::Vec2 as GuiVec;
+use eframe::egui::{Color32, ColorImage, Rect, Stroke};
+use eframe::emath::{Align2, Pos2};
+use eframe::epaint::text::{FontDefinitions, Fonts};
+use eframe::epaint::{
+    tessellator, ClippedShape, FontId, FontImage, ImageData, Mesh, Rounding, Shape,
+    TessellationOptions,
+};
+
+use crate::painter::Painter;
+
+///the biggest side of the font atlas
+const MAX_TEXTURE_SIDE: usize = 2048;
+
+///records everything that is painted and rasterizes it on the cpu into an image
+///one gui pixel is pixels_per_point pixels of the image
+pub(crate) struct RasterPainter {
+    size: [usize; 2],
+    pixels_per_point: f32,
+    clip_rect: Rect,
+    shapes: Vec<ClippedShape>,
+    ///the text is layed out with the default fonts of egui
+    fonts: Fonts,
+    dark_mode: bool,
+}
+
+impl RasterPainter {
+    ///gui_space has to start at (0.0, 0.0)
+    pub(crate) fn new(gui_space: Rect, pixels_per_point: f32, dark_mode: bool) -> RasterPainter {
+        let size = gui_space.size() * pixels_per_point;
+        RasterPainter {
+            size: [size.x.round() as usize, size.y.round() as usize],
+            pixels_per_point,
+            clip_rect: gui_space,
+            shapes: Vec::new(),
+            fonts: Fonts::new(
+                pixels_per_point,
+                MAX_TEXTURE_SIDE,
+                FontDefinitions::default(),
+            ),
+            dark_mode,
+        }
+    }
+
+    pub(crate) fn finish(self) -> ColorImage {
+        //the font atlas is only complete after all text is layed out
+        let font_image_size = self.fonts.font_image_size();
+        let mut font_image = FontImage::new(font_image_size);
+        if let Some(delta) = self.fonts.font_image_delta() {
+            if let ImageData::Font(patch) = delta.image {
+                let [x, y] = delta.pos.unwrap_or([0, 0]);
+                for row in 0..patch.size[1] {
+                    let start = (y + row) * font_image_size[0] + x;
+                    let patch_start = row * patch.size[0];
+                    font_image.pixels[start..start + patch.size[0]]
+                        .copy_from_slice(&patch.pixels[patch_start..patch_start + patch.size[0]]);
+                }
+            }
+        }
+
+        let primitives = tessellator::tessellate_shapes(
+            self.pixels_per_point,
+            TessellationOptions::default(),
+            self.shapes,
+            font_image_size,
+        );
+
+        let mut image = ColorImage::new(self.size, Color32::TRANSPARENT);
+        for primitive in primitives {
+            if let eframe::epaint::Primitive::Mesh(mesh) = primitive.primitive {
+                rasterize(
+                    &mut image,
+                    &mesh,
+                    primitive.clip_rect,
+                    self.pixels_per_point,
+                    &font_image,
+                );
+            }
+        }
+        image
+    }
+
+    fn add(&mut self, shape: impl Into<Shape>) {
+        self.shapes.push(ClippedShape(self.clip_rect, shape.into()));
+    }
+}
+
+///paints the triangles of the mesh (in gui space) over the image
+///the colors are premultiplied so they are blended with "over"
+///every mesh samples the font image (the white pixel for everything but text)
+fn rasterize(
+    image: &mut ColorImage,
+    mesh: &Mesh,
+    clip_rect: Rect,
+    pixels_per_point: f32,
+    font_image: &FontImage,
+) {
+    let [width, height] = image.size;
+    let to_pixels = |pos: Pos2| (pos.to_vec2() * pixels_per_point).to_pos2();
+    let clip_rect = Rect::from_min_max(to_pixels(clip_rect.min), to_pixels(clip_rect.max));
+    let clip_rect = clip_rect.intersect(Rect::from_min_size(
+        Pos2::ZERO,
+        GuiVec::new(width as f32, height as f32),
+    ));
+    if !clip_rect.is_positive() {
+        return;
+    }
+
+    let coverage = |uv: Pos2| {
+        let [font_width, font_height] = font_image.size;
+        let x = ((uv.x * font_width as f32) as usize).min(font_width - 1);
+        let y = ((uv.y * font_height as f32) as usize).min(font_height - 1);
+        font_image.pixels[y * font_width + x]
+    };
+
+    for triangle in mesh.indices.chunks_exact(3) {
+        let [a, b, c] = [0, 1, 2].map(|corner| {
+            let mut vertex = mesh.vertices[triangle[corner] as usize];
+            vertex.pos = to_pixels(vertex.pos);
+            vertex
+        });
+
+        let area = (b.pos - a.pos).x * (c.pos - a.pos).y - (b.pos - a.pos).y * (c.pos - a.pos).x;
+        if area == 0.0 {
+            continue;
+        }
+
+        let bounds = Rect::from_points(&[a.pos, b.pos, c.pos]).intersect(clip_rect);
+        let min_x = bounds.min.x.floor().max(0.0) as usize;
+        let min_y = bounds.min.y.floor().max(0.0) as usize;
+        let max_x = (bounds.max.x.ceil() as usize).min(width);
+        let max_y = (bounds.max.y.ceil() as usize).min(height);
+
+        for y in min_y..max_y {
+            for x in min_x..max_x {
+                //sample in the center of the pixel
+                let pos = Pos2::new(x as f32 + 0.5, y as f32 + 0.5);
+                let edge = |from: Pos2, to: Pos2| {
+                    ((to - from).x * (pos - from).y - (to - from).y * (pos - from).x) / area
+                };
+                let weight_a = edge(b.pos, c.pos);
+                let weight_b = edge(c.pos, a.pos);
+                let weight_c = edge(a.pos, b.pos);
+                if weight_a < 0.0 || weight_b < 0.0 || weight_c < 0.0 {
+                    continue;
+                }
+
+                let uv = (a.uv.to_vec2() * weight_a
+                    + b.uv.to_vec2() * weight_b
+                    + c.uv.to_vec2() * weight_c)
+                    .to_pos2();
+                let coverage = coverage(uv);
+                let source = [0, 1, 2, 3].map(|channel| {
+                    (a.color[channel] as f32 * weight_a
+                        + b.color[channel] as f32 * weight_b
+                        + c.color[channel] as f32 * weight_c)
+                        * coverage
+                });
+
+                let destination = &mut image.pixels[y * width + x];
+                let transparency = 1.0 - source[3] / 255.0;
+                let [r, g, b, a] = [0, 1, 2, 3].map(|channel| {
+                    (source[channel] + destination[channel] as f32 * transparency)
+                        .round()
+                        .clamp(0.0, 255.0) as u8
+                });
+                *destination = Color32::from_rgba_premultiplied(r, g, b, a);
+            }
+        }
+    }
+}
+
+impl Painter for RasterPainter {
+    fn line_segment(&mut self, points: [Pos2; 2], stroke: Stroke) {
+        self.add(Shape::line_segment(points, stroke));
+    }
+
+    fn polyline(&mut self, points: Vec<Pos2>, stroke: Stroke) {
+        self.add(Shape::line(points, stroke));
+    }
+
+    fn circle_filled(&mut self, center: Pos2, radius: f32, fill_color: Color32) {
+        self.add(Shape::circle_filled(center, radius, fill_color));
+    }
+
+    fn rect(&mut self, rect: Rect, rounding: Rounding, fill_color: Color32, stroke: Stroke) {
+        self.add(Shape::rect_filled(rect, rounding, fill_color));
+        self.add(Shape::rect_stroke(rect, rounding, stroke));
+    }
+
+    fn polygon(&mut self, points: Vec<Pos2>, fill_color: Color32, stroke: Stroke) {
+        self.add(Shape::convex_polygon(points, fill_color, stroke));
+    }
+
+    fn mesh(&mut self, mesh: Mesh) {
+        self.add(Shape::mesh(mesh));
+    }
+
+    fn text(
+        &mut self,
+        pos: Pos2,
+        anchor: Align2,
+        text: String,
+        font_id: FontId,
+        text_color: Color32,
+    ) {
+        self.text_galley(pos, anchor, text, font_id, text_color);
+    }
+
+    fn text_size(&self, text: String, font_id: FontId) -> GuiVec {
+        self.fonts
+            .layout_no_wrap(text, font_id, Color32::BLACK)
+            .size()
+    }
+
+    fn text_galley(
+        &mut self,
+        pos: Pos2,
+        anchor: Align2,
+        text: String,
+        font_id: FontId,
+        text_color: Color32,
+    ) -> GuiVec {
+        let galley = self.fonts.layout_no_wrap(text, font_id, text_color);
+        let size = galley.size();
+        let rect = anchor.anchor_rect(Rect::from_min_size(pos, size));
+        self.add(Shape::galley(rect.min, galley));
+        size
+    }
+
+    fn clip_rect(&self) -> Rect {
+        self.clip_rect
+    }
+
+    fn set_clip_rect(&mut self, clip_rect: Rect) {
+        self.clip_rect = clip_rect;
+    }
+
+    fn dark_mode(&self) -> bool {
+        self.dark_mode
+    }
+}
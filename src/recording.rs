@@ -0,0 +1,240 @@
+use eframe::egui::Vec2 as GuiVec;
+use eframe::egui::{Color32, Rect, Stroke};
+use eframe::emath::{Align2, Pos2};
+use eframe::epaint::{FontId, Mesh, Rounding};
+
+#[cfg(feature = "serde")]
+use crate::painter::average_color;
+use crate::painter::{estimate_text_size, Painter};
+
+///a primitive painted by a Drawable
+///everything is in gui space
+#[derive(Debug, Clone, PartialEq)]
+pub enum Primitive {
+    LineSegment {
+        points: [Pos2; 2],
+        stroke: Stroke,
+    },
+    Polyline {
+        points: Vec<Pos2>,
+        stroke: Stroke,
+    },
+    CircleFilled {
+        center: Pos2,
+        radius: f32,
+        fill_color: Color32,
+    },
+    Rect {
+        rect: Rect,
+        rounding: Rounding,
+        fill_color: Color32,
+        stroke: Stroke,
+    },
+    Polygon {
+        points: Vec<Pos2>,
+        fill_color: Color32,
+        stroke: Stroke,
+    },
+    Text {
+        pos: Pos2,
+        anchor: Align2,
+        text: String,
+        font_id: FontId,
+        text_color: Color32,
+    },
+    Mesh(Mesh),
+    ///everything after this is clipped to the rect
+    ClipRect(Rect),
+}
+
+///a primitive with its positions in canvas space
+///used to dump the painted shapes for debugging
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize)]
+pub(crate) struct DumpedShape {
+    kind: &'static str,
+    points: Vec<[f32; 2]>,
+    ///srgba unmultiplied
+    color: [u8; 4],
+    ///in gui space
+    #[serde(skip_serializing_if = "Option::is_none")]
+    radius: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    text: Option<String>,
+}
+
+impl Primitive {
+    ///None for primitives that are not painted (e.g. ClipRect)
+    #[cfg(feature = "serde")]
+    pub(crate) fn dump(&self, to_canvas_space: impl Fn(Pos2) -> Pos2) -> Option<DumpedShape> {
+        let point = |pos: Pos2| {
+            let pos = to_canvas_space(pos);
+            [pos.x, pos.y]
+        };
+        let shape = match self {
+            Primitive::LineSegment { points, stroke } => DumpedShape {
+                kind: "line_segment",
+                points: points.iter().copied().map(point).collect(),
+                color: stroke.color.to_srgba_unmultiplied(),
+                radius: None,
+                text: None,
+            },
+            Primitive::Polyline { points, stroke } => DumpedShape {
+                kind: "polyline",
+                points: points.iter().copied().map(point).collect(),
+                color: stroke.color.to_srgba_unmultiplied(),
+                radius: None,
+                text: None,
+            },
+            Primitive::CircleFilled {
+                center,
+                radius,
+                fill_color,
+            } => DumpedShape {
+                kind: "circle_filled",
+                points: vec![point(*center)],
+                color: fill_color.to_srgba_unmultiplied(),
+                radius: Some(*radius),
+                text: None,
+            },
+            Primitive::Rect {
+                rect, fill_color, ..
+            } => DumpedShape {
+                kind: "rect",
+                points: vec![point(rect.min), point(rect.max)],
+                color: fill_color.to_srgba_unmultiplied(),
+                radius: None,
+                text: None,
+            },
+            Primitive::Polygon {
+                points, fill_color, ..
+            } => DumpedShape {
+                kind: "polygon",
+                points: points.iter().copied().map(point).collect(),
+                color: fill_color.to_srgba_unmultiplied(),
+                radius: None,
+                text: None,
+            },
+            Primitive::Text {
+                pos,
+                text,
+                text_color,
+                ..
+            } => DumpedShape {
+                kind: "text",
+                points: vec![point(*pos)],
+                color: text_color.to_srgba_unmultiplied(),
+                radius: None,
+                text: Some(text.clone()),
+            },
+            Primitive::Mesh(mesh) => DumpedShape {
+                kind: "mesh",
+                points: mesh
+                    .vertices
+                    .iter()
+                    .map(|vertex| point(vertex.pos))
+                    .collect(),
+                color: average_color(mesh.vertices.iter().map(|vertex| vertex.color))
+                    .to_srgba_unmultiplied(),
+                radius: None,
+                text: None,
+            },
+            Primitive::ClipRect(_) => return None,
+        };
+        Some(shape)
+    }
+}
+
+///headless painter that records everything that is painted
+///useful for tests and exporting
+pub struct RecordingPainter {
+    clip_rect: Rect,
+    primitives: Vec<Primitive>,
+}
+
+impl RecordingPainter {
+    pub fn new(gui_space: Rect) -> RecordingPainter {
+        RecordingPainter {
+            clip_rect: gui_space,
+            primitives: Vec::new(),
+        }
+    }
+
+    pub fn primitives(&self) -> &[Primitive] {
+        &self.primitives
+    }
+
+    pub fn into_primitives(self) -> Vec<Primitive> {
+        self.primitives
+    }
+}
+
+impl Painter for RecordingPainter {
+    fn line_segment(&mut self, points: [Pos2; 2], stroke: Stroke) {
+        self.primitives
+            .push(Primitive::LineSegment { points, stroke });
+    }
+
+    fn polyline(&mut self, points: Vec<Pos2>, stroke: Stroke) {
+        self.primitives.push(Primitive::Polyline { points, stroke });
+    }
+
+    fn circle_filled(&mut self, center: Pos2, radius: f32, fill_color: Color32) {
+        self.primitives.push(Primitive::CircleFilled {
+            center,
+            radius,
+            fill_color,
+        });
+    }
+
+    fn rect(&mut self, rect: Rect, rounding: Rounding, fill_color: Color32, stroke: Stroke) {
+        self.primitives.push(Primitive::Rect {
+            rect,
+            rounding,
+            fill_color,
+            stroke,
+        });
+    }
+
+    fn polygon(&mut self, points: Vec<Pos2>, fill_color: Color32, stroke: Stroke) {
+        self.primitives.push(Primitive::Polygon {
+            points,
+            fill_color,
+            stroke,
+        });
+    }
+
+    fn mesh(&mut self, mesh: Mesh) {
+        self.primitives.push(Primitive::Mesh(mesh));
+    }
+
+    fn text(
+        &mut self,
+        pos: Pos2,
+        anchor: Align2,
+        text: String,
+        font_id: FontId,
+        text_color: Color32,
+    ) {
+        self.primitives.push(Primitive::Text {
+            pos,
+            anchor,
+            text,
+            font_id,
+            text_color,
+        });
+    }
+
+    fn text_size(&self, text: String, font_id: FontId) -> GuiVec {
+        estimate_text_size(&text, &font_id)
+    }
+
+    fn clip_rect(&self) -> Rect {
+        self.clip_rect
+    }
+
+    fn set_clip_rect(&mut self, clip_rect: Rect) {
+        self.clip_rect = clip_rect;
+        self.primitives.push(Primitive::ClipRect(clip_rect));
+    }
+}
@@ -0,0 +1,288 @@
+use std::fmt::Write;
+
+use eframe::egui::Vec2 as GuiVec;
+use eframe::egui::{Color32, Rect, Stroke};
+use eframe::emath::{Align, Align2, Pos2};
+use eframe::epaint::{FontFamily, FontId, Mesh, Rounding};
+
+use simple_math::Vec2;
+
+use crate::painter::{average_color, estimate_text_size, Painter};
+use crate::{sanitize_cutout, CanvasHandle, Drawable, Margins, PickRadiusMode};
+
+///renders the drawable into an svg of the given size showing the cutout (in canvas space)
+///needs no CanvasState or egui context so it can be used headless (e.g. in report generators)
+///a degenerated cutout (e.g. of a single point) is sanitized like the cutout of a CanvasState
+pub fn render<D, E>(drawable: &mut E, draw_data: &D, size: Vec2, cutout: Rect) -> String
+where
+    E: Drawable<DrawData = D>,
+{
+    let gui_space = Rect::from_min_size(Pos2::ZERO, size.into());
+    let mut painter = SvgPainter::new(gui_space);
+    let mut cutout = sanitize_cutout(cutout);
+    let mut canvas_handle = CanvasHandle::new(
+        &mut painter,
+        &mut cutout,
+        gui_space,
+        1.0,
+        Margins::default(),
+        PickRadiusMode::default(),
+    );
+    drawable.draw(&mut canvas_handle, draw_data);
+
+    painter.finish()
+}
+
+///records everything that is painted and serializes it to svg
+pub(crate) struct SvgPainter {
+    size: GuiVec,
+    clip_rect: Rect,
+    elements: String,
+    num_clip_paths: usize,
+}
+
+impl SvgPainter {
+    pub(crate) fn new(gui_space: Rect) -> SvgPainter {
+        let mut painter = SvgPainter {
+            size: gui_space.size(),
+            clip_rect: gui_space,
+            elements: String::new(),
+            num_clip_paths: 0,
+        };
+        painter.open_clip_group();
+        painter
+    }
+
+    pub(crate) fn finish(mut self) -> String {
+        //close the last clip group
+        self.elements.push_str("</g>\n");
+
+        format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\n{elements}</svg>\n",
+            w = self.size.x,
+            h = self.size.y,
+            elements = self.elements,
+        )
+    }
+
+    fn open_clip_group(&mut self) {
+        let id = self.num_clip_paths;
+        self.num_clip_paths += 1;
+        let rect = self.clip_rect;
+        //writing into a String can not fail
+        let _ = writeln!(
+            self.elements,
+            "<clipPath id=\"clip{id}\"><rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\"/></clipPath>",
+            rect.min.x,
+            rect.min.y,
+            rect.width(),
+            rect.height(),
+        );
+        let _ = writeln!(self.elements, "<g clip-path=\"url(#clip{id})\">");
+    }
+
+    fn fill(color: Color32) -> String {
+        let [r, g, b, a] = color.to_srgba_unmultiplied();
+        if a == 0 {
+            "fill=\"none\"".to_string()
+        } else {
+            format!(
+                "fill=\"rgb({r},{g},{b})\" fill-opacity=\"{}\"",
+                a as f32 / 255.0
+            )
+        }
+    }
+
+    fn stroke(stroke: Stroke) -> String {
+        let [r, g, b, a] = stroke.color.to_srgba_unmultiplied();
+        if a == 0 || stroke.width <= 0.0 {
+            "stroke=\"none\"".to_string()
+        } else {
+            format!(
+                "stroke=\"rgb({r},{g},{b})\" stroke-opacity=\"{}\" stroke-width=\"{}\"",
+                a as f32 / 255.0,
+                stroke.width
+            )
+        }
+    }
+
+    fn escape(text: &str) -> String {
+        text.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+    }
+}
+
+impl Painter for SvgPainter {
+    fn line_segment(&mut self, points: [Pos2; 2], stroke: Stroke) {
+        let [a, b] = points;
+        let _ = writeln!(
+            self.elements,
+            "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" {}/>",
+            a.x,
+            a.y,
+            b.x,
+            b.y,
+            SvgPainter::stroke(stroke),
+        );
+    }
+
+    fn polyline(&mut self, points: Vec<Pos2>, stroke: Stroke) {
+        let points: Vec<String> = points
+            .iter()
+            .map(|point| format!("{},{}", point.x, point.y))
+            .collect();
+        let _ = writeln!(
+            self.elements,
+            "<polyline points=\"{}\" fill=\"none\" {}/>",
+            points.join(" "),
+            SvgPainter::stroke(stroke),
+        );
+    }
+
+    fn circle_filled(&mut self, center: Pos2, radius: f32, fill_color: Color32) {
+        let _ = writeln!(
+            self.elements,
+            "<circle cx=\"{}\" cy=\"{}\" r=\"{radius}\" {}/>",
+            center.x,
+            center.y,
+            SvgPainter::fill(fill_color),
+        );
+    }
+
+    fn rect(&mut self, rect: Rect, rounding: Rounding, fill_color: Color32, stroke: Stroke) {
+        //svg only supports one radius for all corners
+        let radius = rounding
+            .nw
+            .max(rounding.ne)
+            .max(rounding.sw)
+            .max(rounding.se);
+        let _ = writeln!(
+            self.elements,
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"{radius}\" {} {}/>",
+            rect.min.x,
+            rect.min.y,
+            rect.width(),
+            rect.height(),
+            SvgPainter::fill(fill_color),
+            SvgPainter::stroke(stroke),
+        );
+    }
+
+    fn polygon(&mut self, points: Vec<Pos2>, fill_color: Color32, stroke: Stroke) {
+        let points: Vec<String> = points
+            .iter()
+            .map(|point| format!("{},{}", point.x, point.y))
+            .collect();
+        let _ = writeln!(
+            self.elements,
+            "<polygon points=\"{}\" {} {}/>",
+            points.join(" "),
+            SvgPainter::fill(fill_color),
+            SvgPainter::stroke(stroke),
+        );
+    }
+
+    ///svg has no per vertex colors so every triangle gets the average color of its vertices
+    fn mesh(&mut self, mesh: Mesh) {
+        for triangle in mesh.indices.chunks_exact(3) {
+            let vertices = triangle.iter().map(|index| mesh.vertices[*index as usize]);
+            let points: Vec<String> = vertices
+                .clone()
+                .map(|vertex| format!("{},{}", vertex.pos.x, vertex.pos.y))
+                .collect();
+            let color = average_color(vertices.map(|vertex| vertex.color));
+            let _ = writeln!(
+                self.elements,
+                "<polygon points=\"{}\" {}/>",
+                points.join(" "),
+                SvgPainter::fill(color),
+            );
+        }
+    }
+
+    fn text(
+        &mut self,
+        pos: Pos2,
+        anchor: Align2,
+        text: String,
+        font_id: FontId,
+        text_color: Color32,
+    ) {
+        let text_anchor = match anchor.x() {
+            Align::Min => "start",
+            Align::Center => "middle",
+            Align::Max => "end",
+        };
+        let baseline = match anchor.y() {
+            Align::Min => "hanging",
+            Align::Center => "central",
+            Align::Max => "text-after-edge",
+        };
+        let font_family = match font_id.family {
+            FontFamily::Monospace => "monospace",
+            _ => "sans-serif",
+        };
+        let _ = writeln!(
+            self.elements,
+            "<text x=\"{}\" y=\"{}\" font-family=\"{font_family}\" font-size=\"{}\" text-anchor=\"{text_anchor}\" dominant-baseline=\"{baseline}\" {}>{}</text>",
+            pos.x,
+            pos.y,
+            font_id.size,
+            SvgPainter::fill(text_color),
+            SvgPainter::escape(&text),
+        );
+    }
+
+    fn text_size(&self, text: String, font_id: FontId) -> GuiVec {
+        estimate_text_size(&text, &font_id)
+    }
+
+    fn clip_rect(&self) -> Rect {
+        self.clip_rect
+    }
+
+    fn set_clip_rect(&mut self, clip_rect: Rect) {
+        self.clip_rect = clip_rect;
+        self.elements.push_str("</g>\n");
+        self.open_clip_group();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use eframe::emath::pos2;
+
+    use super::*;
+    use crate::Position;
+
+    ///a line from the origin to (1.0, 1.0)
+    struct Diagonal;
+
+    impl Drawable for Diagonal {
+        type DrawData = ();
+
+        fn draw(&mut self, handle: &mut CanvasHandle, _draw_data: &()) {
+            let start = Position::Canvas(pos2(0.0, 0.0));
+            let end = Position::Canvas(pos2(1.0, 1.0));
+            handle.line_segment((start, end), (1.0, Color32::RED));
+        }
+
+        fn get_cutout(&mut self, _draw_data: &()) -> Rect {
+            Rect::from_min_max(pos2(0.0, 0.0), pos2(1.0, 1.0))
+        }
+    }
+
+    #[test]
+    fn degenerated_cutouts_render_without_nan() {
+        let point = Rect::from_min_max(pos2(0.5, 0.5), pos2(0.5, 0.5));
+        let zero_width = Rect::from_min_max(pos2(0.5, 0.0), pos2(0.5, 1.0));
+        let nan = Rect::from_min_max(pos2(f32::NAN, 0.0), pos2(1.0, 1.0));
+        for cutout in [point, zero_width, nan, Rect::NOTHING] {
+            let svg = render(&mut Diagonal, &(), Vec2::new(200.0, 100.0), cutout);
+            assert!(svg.contains("<line"), "{svg}");
+            assert!(!svg.contains("NaN") && !svg.contains("inf"), "{svg}");
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use std::ops::Range;
+
+use eframe::egui::Vec2 as GuiVec;
+use eframe::egui::{Pos2, Rect};
+use simple_math::Vec2;
+
+///an affine transform in canvas space
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transform {
+    ///where the unit vectors end up
+    x_axis: GuiVec,
+    y_axis: GuiVec,
+    translation: GuiVec,
+}
+
+impl Transform {
+    pub const IDENTITY: Transform = Transform {
+        x_axis: GuiVec { x: 1.0, y: 0.0 },
+        y_axis: GuiVec { x: 0.0, y: 1.0 },
+        translation: GuiVec::ZERO,
+    };
+
+    pub fn translation(translation: Vec2) -> Transform {
+        Transform {
+            translation: translation.into(),
+            ..Transform::IDENTITY
+        }
+    }
+
+    pub fn scale(scale: Vec2) -> Transform {
+        Transform {
+            x_axis: GuiVec::new(scale.x(), 0.0),
+            y_axis: GuiVec::new(0.0, scale.y()),
+            translation: GuiVec::ZERO,
+        }
+    }
+
+    ///maps the y values in from onto to and keeps the x values
+    ///e.g. to draw data with its own range against a secondary y axis
+    pub fn map_y_range(from: Range<f32>, to: Range<f32>) -> Transform {
+        let scale = (to.end - to.start) / (from.end - from.start);
+        Transform {
+            x_axis: GuiVec::new(1.0, 0.0),
+            y_axis: GuiVec::new(0.0, scale),
+            translation: GuiVec::new(0.0, to.start - from.start * scale),
+        }
+    }
+
+    ///counter clockwise rotation in radians around the origin
+    pub fn rotation(angle: f32) -> Transform {
+        let (sin, cos) = angle.sin_cos();
+        Transform {
+            x_axis: GuiVec::new(cos, sin),
+            y_axis: GuiVec::new(-sin, cos),
+            translation: GuiVec::ZERO,
+        }
+    }
+
+    ///first applies self and then next
+    pub fn then(self, next: Transform) -> Transform {
+        Transform {
+            x_axis: next.apply_linear(self.x_axis),
+            y_axis: next.apply_linear(self.y_axis),
+            translation: next.apply(self.translation.to_pos2()).to_vec2(),
+        }
+    }
+
+    pub fn apply(self, pos: Pos2) -> Pos2 {
+        (self.apply_linear(pos.to_vec2()) + self.translation).to_pos2()
+    }
+
+    ///transforms a direction or displacement so the translation is ignored
+    pub fn apply_to_vec(self, vec: Vec2) -> Vec2 {
+        self.apply_linear(vec.into()).into()
+    }
+
+    ///the bounding box of the transformed rect
+    pub fn apply_to_rect(self, rect: Rect) -> Rect {
+        let corners = [
+            rect.left_bottom(),
+            rect.right_bottom(),
+            rect.left_top(),
+            rect.right_top(),
+        ];
+        Rect::from_points(&corners.map(|corner| self.apply(corner)))
+    }
+
+    ///None if the transform squashes everything onto a line or point
+    pub fn inverse(self) -> Option<Transform> {
+        let determinant = self.x_axis.x * self.y_axis.y - self.y_axis.x * self.x_axis.y;
+        if determinant == 0.0 || !determinant.is_finite() {
+            return None;
+        }
+        let linear = Transform {
+            x_axis: GuiVec::new(self.y_axis.y, -self.x_axis.y) / determinant,
+            y_axis: GuiVec::new(-self.y_axis.x, self.x_axis.x) / determinant,
+            translation: GuiVec::ZERO,
+        };
+        Some(Transform {
+            translation: -linear.apply_linear(self.translation),
+            ..linear
+        })
+    }
+
+    fn apply_linear(self, vec: GuiVec) -> GuiVec {
+        self.x_axis * vec.x + self.y_axis * vec.y
+    }
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Transform::IDENTITY
+    }
+}
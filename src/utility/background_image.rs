@@ -0,0 +1,76 @@
+use std::marker::PhantomData;
+
+use eframe::emath::Rect;
+use egui_extras::RetainedImage;
+
+use crate::{CanvasHandle, Drawable, Position, BACKGROUND_Z_ORDER};
+
+///an image fixed in canvas space that pans and zooms with the canvas
+///it has the BACKGROUND_Z_ORDER so it is drawn below the data
+///its bounds are its cutout so resetting fits the image
+pub struct BackgroundImage<D> {
+    image: RetainedImage,
+    ///in canvas space
+    bounds: Rect,
+    ///keep the aspect ratio of the image by centering it in the bounds
+    letterbox: bool,
+    phantom: PhantomData<D>,
+}
+
+impl<D> BackgroundImage<D> {
+    pub fn new(image: RetainedImage, bounds: Rect) -> BackgroundImage<D> {
+        BackgroundImage {
+            image,
+            bounds,
+            letterbox: false,
+            phantom: PhantomData,
+        }
+    }
+
+    ///keep the aspect ratio of the image (in canvas space) by centering it in the bounds
+    ///instead of stretching it to the bounds
+    pub fn with_letterbox(mut self, letterbox: bool) -> BackgroundImage<D> {
+        self.letterbox = letterbox;
+        self
+    }
+
+    pub fn set_bounds(&mut self, bounds: Rect) {
+        self.bounds = bounds;
+    }
+
+    ///the region in canvas space the image is drawn in
+    fn image_rect(&self) -> Rect {
+        let [width, height] = self.image.size();
+        if !self.letterbox || width == 0 || height == 0 {
+            return self.bounds;
+        }
+
+        let image_aspect_ratio = width as f32 / height as f32;
+        let mut size = self.bounds.size();
+        if self.bounds.aspect_ratio() > image_aspect_ratio {
+            size.x = size.y * image_aspect_ratio;
+        } else {
+            size.y = size.x / image_aspect_ratio;
+        }
+        Rect::from_center_size(self.bounds.center(), size)
+    }
+}
+
+impl<D> Drawable for BackgroundImage<D> {
+    type DrawData = D;
+
+    fn draw(&mut self, handle: &mut CanvasHandle, _draw_data: &D) {
+        let image_rect = self.image_rect();
+        let corner_a = Position::Canvas(image_rect.min);
+        let corner_b = Position::Canvas(image_rect.max);
+        handle.image(&self.image, corner_a, corner_b);
+    }
+
+    fn get_cutout(&mut self, _draw_data: &D) -> Rect {
+        self.bounds
+    }
+
+    fn z_order(&self) -> i32 {
+        BACKGROUND_Z_ORDER
+    }
+}
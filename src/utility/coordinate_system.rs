@@ -1,5 +1,11 @@
 #![allow(dead_code)]
-use std::{cmp::min, marker::PhantomData};
+use std::{
+    cmp::min,
+    fmt,
+    marker::PhantomData,
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
 
 use eframe::{
     emath::{Align2, Pos2, Rect},
@@ -7,7 +13,8 @@ use eframe::{
 };
 use simple_math::Rectangle;
 
-use crate::{CanvasHandle, Drawable, Position};
+use super::time_axis::{format_time, nice_time_interval, seconds_since_epoch};
+use crate::{CanvasHandle, Drawable, Position, Transform, OVERLAY_Z_ORDER};
 
 const DEFAULT_PADDING: f32 = 60.0;
 const THICK_LINE_WIDTH: f32 = 1.0;
@@ -17,10 +24,19 @@ const MAYOR_TICK_STROKE_LENGHT: f32 = 4.0;
 
 const MIN_NUMBER_OF_TICKS: u8 = 4;
 
+///the smallest space between two automatic mayor ticks in gui pixels
+const MIN_TICK_SPACING: f32 = 30.0;
+
+const DEFAULT_NUMBER_OF_TIME_TICKS: u8 = 5;
+
 #[derive(Debug)]
 pub struct CoordinateSystem<D> {
     x_axis: Option<Axis>,
     y_axis: Option<Axis>,
+    ///an axis on the right with its own scale
+    y_axis_secondary: Option<Axis>,
+    ///maps the values of the secondary y axis to canvas space
+    secondary_y_transform: Transform,
     phantom: PhantomData<D>,
 }
 
@@ -29,6 +45,8 @@ impl<D> CoordinateSystem<D> {
         CoordinateSystem {
             x_axis: Some(Axis::default()),
             y_axis: Some(Axis::default()),
+            y_axis_secondary: None,
+            secondary_y_transform: Transform::IDENTITY,
             phantom: PhantomData,
         }
     }
@@ -37,10 +55,32 @@ impl<D> CoordinateSystem<D> {
         CoordinateSystem {
             x_axis: Some(Axis::default()),
             y_axis: None,
+            y_axis_secondary: None,
+            secondary_y_transform: Transform::IDENTITY,
+            phantom: PhantomData,
+        }
+    }
+
+    pub fn y_axis() -> CoordinateSystem<D> {
+        CoordinateSystem {
+            x_axis: None,
+            y_axis: Some(Axis::default()),
+            y_axis_secondary: None,
+            secondary_y_transform: Transform::IDENTITY,
             phantom: PhantomData,
         }
     }
 
+    pub fn without_x_axis(mut self) -> CoordinateSystem<D> {
+        self.x_axis = None;
+        self
+    }
+
+    pub fn without_y_axis(mut self) -> CoordinateSystem<D> {
+        self.y_axis = None;
+        self
+    }
+
     pub fn with_mayor_tick_interval(mut self, mayor_tick_interval: Tick) -> CoordinateSystem<D> {
         if let Some(ref mut axis) = self.x_axis {
             axis.mayor_tick_interval = Some(mayor_tick_interval);
@@ -65,6 +105,117 @@ impl<D> CoordinateSystem<D> {
         self
     }
 
+    ///draw thin lines at the mayor ticks of both axes
+    pub fn with_lines(mut self, line_style: LineStyle) -> CoordinateSystem<D> {
+        if let Some(ref mut axis) = self.x_axis {
+            axis.lines = Some(line_style);
+        }
+        if let Some(ref mut axis) = self.y_axis {
+            axis.lines = Some(line_style);
+        }
+        self
+    }
+
+    ///the side of the axis lines the mayor tick strokes of both axes are on
+    pub fn with_tick_side(mut self, tick_side: TickSide) -> CoordinateSystem<D> {
+        if let Some(ref mut axis) = self.x_axis {
+            axis.tick_side = tick_side;
+        }
+        if let Some(ref mut axis) = self.y_axis {
+            axis.tick_side = tick_side;
+        }
+        self
+    }
+
+    ///draw at most limit mayor ticks on both axes starting at the first visible tick
+    pub fn with_tick_count_limit(mut self, limit: usize) -> CoordinateSystem<D> {
+        if let Some(ref mut axis) = self.x_axis {
+            axis.length = Some(limit);
+        }
+        if let Some(ref mut axis) = self.y_axis {
+            axis.length = Some(limit);
+        }
+        self
+    }
+
+    pub fn with_lines_x(mut self, line_style: LineStyle) -> CoordinateSystem<D> {
+        if let Some(ref mut axis) = self.x_axis {
+            axis.lines = Some(line_style);
+        }
+        self
+    }
+
+    pub fn with_lines_y(mut self, line_style: LineStyle) -> CoordinateSystem<D> {
+        if let Some(ref mut axis) = self.y_axis {
+            axis.lines = Some(line_style);
+        }
+        self
+    }
+
+    ///format the labels of the mayor ticks of both axes with the formatter
+    pub fn with_label_formatter(
+        mut self,
+        formatter: impl Fn(f32) -> String + 'static,
+    ) -> CoordinateSystem<D> {
+        let formatter = LabelFormatter(Arc::new(formatter));
+        if let Some(ref mut axis) = self.x_axis {
+            axis.label_formatter = Some(formatter.clone());
+        }
+        if let Some(ref mut axis) = self.y_axis {
+            axis.label_formatter = Some(formatter);
+        }
+        self
+    }
+
+    pub fn with_label_formatter_x(
+        mut self,
+        formatter: impl Fn(f32) -> String + 'static,
+    ) -> CoordinateSystem<D> {
+        if let Some(ref mut axis) = self.x_axis {
+            axis.label_formatter = Some(LabelFormatter(Arc::new(formatter)));
+        }
+        self
+    }
+
+    pub fn with_label_formatter_y(
+        mut self,
+        formatter: impl Fn(f32) -> String + 'static,
+    ) -> CoordinateSystem<D> {
+        if let Some(ref mut axis) = self.y_axis {
+            axis.label_formatter = Some(LabelFormatter(Arc::new(formatter)));
+        }
+        self
+    }
+
+    ///the x values are times with value 0.0 at base and 1.0 at base + unit
+    ///the mayor ticks are placed at nice time intervals and labeled as utc times
+    ///choose base close to the data because the canvas only has f32 precision
+    pub fn with_time_axis_x(mut self, base: SystemTime, unit: Duration) -> CoordinateSystem<D> {
+        if let Some(ref mut axis) = self.x_axis {
+            axis.set_time_axis(base, unit);
+        }
+        self
+    }
+
+    pub fn with_time_axis_y(mut self, base: SystemTime, unit: Duration) -> CoordinateSystem<D> {
+        if let Some(ref mut axis) = self.y_axis {
+            axis.set_time_axis(base, unit);
+        }
+        self
+    }
+
+    ///restrict the axes and their ticks to the bounds (in canvas space)
+    ///e.g. the cutout of the data for a boxed plot style
+    pub fn with_bounded_to_data(mut self, bounds: Rect) -> CoordinateSystem<D> {
+        if let Some(ref mut axis) = self.x_axis {
+            axis.bounds = Some(bounds);
+        }
+        if let Some(ref mut axis) = self.y_axis {
+            axis.bounds = Some(bounds);
+        }
+        self
+    }
+
     pub fn with_x_axis_placement(mut self, placment: Placement) -> CoordinateSystem<D> {
         if let Some(ref mut axis) = self.x_axis {
             axis.placement = placment;
@@ -78,6 +229,90 @@ impl<D> CoordinateSystem<D> {
         }
         self
     }
+
+    ///the distance in gui pixels to the border of the canvas
+    ///at which axes placed in the canvas stop following the cutout and stick to the border
+    ///default is 60.0
+    pub fn with_embedded_padding(mut self, padding: f32) -> CoordinateSystem<D> {
+        if let Some(ref mut axis) = self.x_axis {
+            axis.embedded_padding = Some(padding);
+        }
+        if let Some(ref mut axis) = self.y_axis {
+            axis.embedded_padding = Some(padding);
+        }
+        self
+    }
+
+    ///adds a y axis on the right that labels the values transform maps to canvas space
+    ///e.g. Transform::map_y_range(humidity_range, temperature_range)
+    ///the settings are copied from the y axis so configure it before
+    ///draw the data of the secondary axis between handle.push_transform(transform)
+    ///and handle.pop_transform() (see secondary_y_transform)
+    pub fn with_secondary_y_axis(mut self, transform: Transform) -> CoordinateSystem<D> {
+        let mut axis = self.y_axis.clone().unwrap_or_default();
+        axis.placement = Placement::Overlay(Alignment::RightOrTop(DEFAULT_PADDING));
+        axis.lines = None;
+        //the bounds are in canvas space and not in the space of the secondary axis
+        axis.bounds = None;
+        axis.mirrored = true;
+        self.y_axis_secondary = Some(axis);
+        self.secondary_y_transform = transform;
+        self
+    }
+
+    ///the transform of the secondary y axis to draw its data with
+    ///None if there is no secondary y axis
+    pub fn secondary_y_transform(&self) -> Option<Transform> {
+        self.y_axis_secondary
+            .as_ref()
+            .map(|_axis| self.secondary_y_transform)
+    }
+
+    pub fn with_label_formatter_y_secondary(
+        mut self,
+        formatter: impl Fn(f32) -> String + 'static,
+    ) -> CoordinateSystem<D> {
+        if let Some(ref mut axis) = self.y_axis_secondary {
+            axis.label_formatter = Some(LabelFormatter(Arc::new(formatter)));
+        }
+        self
+    }
+
+    ///style of the x axis line
+    ///color None for the theme color
+    pub fn with_x_axis_style(
+        mut self,
+        width: f32,
+        color: Option<Color32>,
+        line_style: LineStyle,
+    ) -> CoordinateSystem<D> {
+        if let Some(ref mut axis) = self.x_axis {
+            axis.axis_line = AxisLine {
+                width,
+                color,
+                line_style,
+            };
+        }
+        self
+    }
+
+    ///style of the y axis line
+    ///color None for the theme color
+    pub fn with_y_axis_style(
+        mut self,
+        width: f32,
+        color: Option<Color32>,
+        line_style: LineStyle,
+    ) -> CoordinateSystem<D> {
+        if let Some(ref mut axis) = self.y_axis {
+            axis.axis_line = AxisLine {
+                width,
+                color,
+                line_style,
+            };
+        }
+        self
+    }
 }
 
 impl<D> Default for CoordinateSystem<D> {
@@ -90,17 +325,23 @@ impl<D> Drawable for CoordinateSystem<D> {
     type DrawData = D;
 
     fn draw(&mut self, handle: &mut CanvasHandle, _draw_data: &D) {
-        let color = if handle.dark_mode() {
-            Color32::WHITE
-        } else {
-            Color32::BLACK
-        };
+        let color = handle.foreground_color();
 
         if let Some(ref mut axis) = self.x_axis {
-            axis.draw(handle, color, Kind::X);
+            axis.draw(handle, color, Kind::X, 1.0);
         }
         if let Some(ref mut axis) = self.y_axis {
-            axis.draw(handle, color, Kind::Y);
+            axis.draw(handle, color, Kind::Y, 1.0);
+        }
+        if let Some(ref mut axis) = self.y_axis_secondary {
+            let transform = self.secondary_y_transform;
+            //the canvas units per unit of the secondary axis
+            let unit_scale = (transform.apply(Pos2::new(0.0, 1.0)) - transform.apply(Pos2::ZERO))
+                .y
+                .abs();
+            handle.push_transform(transform);
+            axis.draw(handle, color, Kind::Y, unit_scale);
+            handle.pop_transform();
         }
     }
 
@@ -108,6 +349,10 @@ impl<D> Drawable for CoordinateSystem<D> {
         //Coordinate System is an overlay so there is no cutout
         Rect::NOTHING
     }
+
+    fn z_order(&self) -> i32 {
+        OVERLAY_Z_ORDER
+    }
 }
 
 #[derive(Debug, Clone, Default)]
@@ -119,29 +364,108 @@ pub struct Axis {
     ///the interval for the mayor ticks None for no mayor ticks
     mayor_tick_interval: Option<Tick>,
 
-    ///draw thin lines at the mayor tick interval
+    ///draw thin lines at the mayor tick interval None for no lines
     ///has only affect if mayor_tick_interval is Some
-    ///todo unimplmented
-    lines: bool,
+    lines: Option<LineStyle>,
 
     ///labeling for the axis
     ///todo unimplmented
     label: String,
 
     ///the number of mayor ticks to do None for infinity
-    ///todo unimplmented
+    ///the ticks are counted from the first visible tick
     length: Option<usize>,
 
     ///positon of the axis
     placement: Placement,
+
+    ///the padding at which an axis placed in the canvas sticks to the border
+    ///None for DEFAULT_PADDING
+    embedded_padding: Option<f32>,
+
+    ///on which side of the axis line the mayor tick strokes are
+    tick_side: TickSide,
+
+    ///the labels and the outside are right of a y axis (e.g. for a secondary y axis)
+    mirrored: bool,
+
+    ///the region in canvas space the axis is restricted to None for the whole visible region
+    bounds: Option<Rect>,
+
+    ///formats the labels of the mayor ticks None for print_float
+    label_formatter: Option<LabelFormatter>,
+
+    ///the style of the axis line itself
+    axis_line: AxisLine,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct AxisLine {
+    width: f32,
+    ///None for the theme color
+    color: Option<Color32>,
+    line_style: LineStyle,
+}
+
+impl Default for AxisLine {
+    fn default() -> Self {
+        AxisLine {
+            width: THICK_LINE_WIDTH,
+            color: None,
+            line_style: LineStyle::Solid,
+        }
+    }
+}
+
+#[derive(Clone)]
+struct LabelFormatter(Arc<dyn Fn(f32) -> String>);
+
+impl fmt::Debug for LabelFormatter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("LabelFormatter")
+    }
 }
 
 impl Axis {
-    fn draw(&self, handle: &mut CanvasHandle, color: Color32, kind: Kind) {
+    fn set_time_axis(&mut self, base: SystemTime, unit: Duration) {
+        let wanted_num_ticks = match self.mayor_tick_interval {
+            Some(Tick::Automatic(wanted_num_ticks))
+            | Some(Tick::AutomaticTime {
+                wanted_num_ticks, ..
+            }) => wanted_num_ticks,
+            _ => DEFAULT_NUMBER_OF_TIME_TICKS,
+        };
+        self.mayor_tick_interval = Some(Tick::AutomaticTime {
+            wanted_num_ticks,
+            unit,
+            base,
+        });
+
+        let base = seconds_since_epoch(base);
+        self.label_formatter = Some(LabelFormatter(Arc::new(move |value| {
+            format_time(value, base, unit)
+        })));
+    }
+
+    ///unit_scale is the canvas units per unit of the axis
+    ///because the pixels per canvas unit ignore the transforms of the handle
+    fn draw(&self, handle: &mut CanvasHandle, color: Color32, kind: Kind, unit_scale: f32) {
         let bounding_box = handle.bounding_box();
         //draw the line
-        let points = self.get_line_points(handle, bounding_box, kind);
-        handle.line_segment(points, (THICK_LINE_WIDTH, color));
+        let mut points = self.get_line_points(handle, bounding_box, kind);
+        if let Some(bounds) = self.bounds {
+            match Axis::bound_line_points(handle, points, bounds, kind) {
+                Some(bounded_points) => points = bounded_points,
+                //the axis is not in the bounds
+                None => return,
+            }
+        }
+        let AxisLine {
+            width,
+            color: line_color,
+            line_style,
+        } = self.axis_line;
+        line_style.draw(handle, points, (width, line_color.unwrap_or(color)));
 
         if let Some(mayor_tick_interval) = self.mayor_tick_interval {
             let font_id = FontId {
@@ -150,130 +474,236 @@ impl Axis {
             };
 
             let draw_region = handle.get_draw_region_in_canvas_space();
-            let draw_space = match kind {
-                Kind::X => draw_region.width(),
-                Kind::Y => draw_region.height(),
+            let pixels_per_unit = handle.pixels_per_canvas_unit();
+            let (draw_space, pixels_per_unit) = match kind {
+                Kind::X => (draw_region.width(), pixels_per_unit.x() * unit_scale),
+                Kind::Y => (draw_region.height(), pixels_per_unit.y() * unit_scale),
             };
-            Axis::draw_mayor_ticks(
+            let interval = mayor_tick_interval.get_absolute_tick(draw_space, pixels_per_unit);
+            self.draw_mayor_ticks(
                 handle,
                 color,
                 font_id,
                 points,
-                mayor_tick_interval.get_absolute_tick(draw_space),
+                (interval, mayor_tick_interval.get_offset(interval)),
                 kind,
             );
         }
         //todo draw the rest
     }
 
+    ///restricts the axis line to the bounds
+    ///returns None if the line is not in the bounds
+    fn bound_line_points(
+        handle: &CanvasHandle,
+        points: (Position, Position),
+        bounds: Rect,
+        kind: Kind,
+    ) -> Option<(Position, Position)> {
+        use Position::Canvas;
+        let a = handle.convert_to_canvas_space(points.0).get_raw_pos();
+        let b = handle.convert_to_canvas_space(points.1).get_raw_pos();
+        let line = Rect::from_two_pos(a, b);
+
+        match kind {
+            Kind::X => {
+                let y = line.min.y.clamp(bounds.min.y, bounds.max.y);
+                let left = line.min.x.max(bounds.min.x);
+                let right = line.max.x.min(bounds.max.x);
+                (left <= right).then(|| (Canvas((left, y).into()), Canvas((right, y).into())))
+            }
+            Kind::Y => {
+                let x = line.min.x.clamp(bounds.min.x, bounds.max.x);
+                let bottom = line.min.y.max(bounds.min.y);
+                let top = line.max.y.min(bounds.max.y);
+                (bottom <= top).then(|| (Canvas((x, bottom).into()), Canvas((x, top).into())))
+            }
+        }
+    }
+
     fn draw_mayor_ticks(
+        &self,
         handle: &mut CanvasHandle,
         color: Color32,
         font_id: FontId,
         axis_line: (Position, Position),
-        mayor_tick_interval: f32,
+        (mayor_tick_interval, offset): (f32, f32),
         kind: Kind,
     ) {
         let (start, end) = axis_line;
         let start_on_canvas = handle.convert_to_canvas_space(start).get_raw_pos();
         let end_on_canvas = handle.convert_to_canvas_space(end).get_raw_pos();
 
+        //the extent of the last drawn label in overlay space
+        //labels that would overlap it are skipped but their tick is drawn
+        let mut last_label = None;
+        let max_num_ticks = self.length.unwrap_or(usize::MAX);
+
         use Kind::{X, Y};
         use Position::Canvas;
         match kind {
             X => {
-                let ticks_left_out_of_bounds = start_on_canvas.x / mayor_tick_interval;
-                let first_tick_x = if ticks_left_out_of_bounds > 0.0 {
-                    ticks_left_out_of_bounds.ceil() * mayor_tick_interval
-                } else {
-                    ticks_left_out_of_bounds.trunc() * mayor_tick_interval
-                };
-                let mut tick_x = first_tick_x;
-                while tick_x <= end_on_canvas.x {
+                for tick_x in Tick::positions_with_offset(
+                    mayor_tick_interval,
+                    offset,
+                    start_on_canvas.x,
+                    end_on_canvas.x,
+                )
+                .take(max_num_ticks)
+                {
                     let pos = Canvas(Pos2 {
                         x: tick_x,
                         y: start_on_canvas.y,
                     });
-                    Axis::draw_mayor_tick(handle, color, font_id.clone(), pos, kind);
-                    tick_x += mayor_tick_interval;
+                    self.draw_mayor_tick(
+                        handle,
+                        color,
+                        font_id.clone(),
+                        pos,
+                        kind,
+                        &mut last_label,
+                    );
                 }
             }
             Y => {
-                let ticks_bottom_out_of_bounds = start_on_canvas.y / mayor_tick_interval;
-                let first_tick_y = if ticks_bottom_out_of_bounds > 0.0 {
-                    ticks_bottom_out_of_bounds.ceil() * mayor_tick_interval
-                } else {
-                    ticks_bottom_out_of_bounds.trunc() * mayor_tick_interval
-                };
-                let mut tick_y = first_tick_y;
-                while tick_y <= end_on_canvas.y {
+                for tick_y in Tick::positions_with_offset(
+                    mayor_tick_interval,
+                    offset,
+                    start_on_canvas.y,
+                    end_on_canvas.y,
+                )
+                .take(max_num_ticks)
+                {
                     let pos = Canvas(Pos2 {
                         x: start_on_canvas.x,
                         y: tick_y,
                     });
-                    Axis::draw_mayor_tick(handle, color, font_id.clone(), pos, kind);
-                    tick_y += mayor_tick_interval;
+                    self.draw_mayor_tick(
+                        handle,
+                        color,
+                        font_id.clone(),
+                        pos,
+                        kind,
+                        &mut last_label,
+                    );
                 }
             }
         }
     }
 
     fn draw_mayor_tick(
+        &self,
         handle: &mut CanvasHandle,
         color: Color32,
         font_id: FontId,
         pos: Position,
         kind: Kind,
+        last_label: &mut Option<Rect>,
     ) {
         use Position::Overlay;
         let overlay_pos = handle.convert_to_overlay_space(pos);
         let canvas_pos = handle.convert_to_canvas_space(pos);
         let pos = overlay_pos.get_raw_pos();
         use Kind::{X, Y};
+
+        if let Some(line_style) = self.lines {
+            let bounding_box = handle.bounding_box();
+            let canvas_pos = canvas_pos.get_raw_pos();
+            let line_points = match (kind, self.bounds) {
+                (X, None) => (
+                    Overlay((pos.x, bounding_box.bottom()).into()),
+                    Overlay((pos.x, bounding_box.top()).into()),
+                ),
+                (Y, None) => (
+                    Overlay((bounding_box.left(), pos.y).into()),
+                    Overlay((bounding_box.right(), pos.y).into()),
+                ),
+                (X, Some(bounds)) => (
+                    Position::Canvas((canvas_pos.x, bounds.min.y).into()),
+                    Position::Canvas((canvas_pos.x, bounds.max.y).into()),
+                ),
+                (Y, Some(bounds)) => (
+                    Position::Canvas((bounds.min.x, canvas_pos.y).into()),
+                    Position::Canvas((bounds.max.x, canvas_pos.y).into()),
+                ),
+            };
+            line_style.draw(handle, line_points, (THIN_LINE_WIDTH, color));
+        }
+
+        let (outside_length, inside_length) = self.tick_side.stroke_lengths();
         match kind {
             X => {
                 let pos_bottom = Overlay(Pos2 {
                     x: pos.x,
-                    y: pos.y - MAYOR_TICK_STROKE_LENGHT / 2.0,
+                    y: pos.y - outside_length,
                 });
                 let pos_top = Overlay(Pos2 {
                     x: pos.x,
-                    y: pos.y + MAYOR_TICK_STROKE_LENGHT / 2.0,
+                    y: pos.y + inside_length,
                 });
                 handle.line_segment((pos_bottom, pos_top), (THICK_LINE_WIDTH, color));
 
-                let text = Self::print_float(canvas_pos.get_raw_pos().x);
-                let size = handle.text_size(&text, font_id.clone());
-                let text_pos = Overlay(Pos2 {
+                let text = self.format_label(canvas_pos.get_raw_pos().x);
+                let text_pos = Pos2 {
                     x: pos.x,
                     //subtract the 2.0 for a bit of space between the mayor tick strock and the number text
-                    y: pos.y - size.y() - MAYOR_TICK_STROKE_LENGHT / 2.0 - 2.0,
-                });
-                handle.text(text_pos, Align2::CENTER_BOTTOM, text, font_id, color)
+                    y: pos.y - outside_length - 2.0,
+                };
+                let size = handle.text_size(&text, font_id.clone());
+                //the overlay y points up so the text is below text_pos
+                let label = Rect::from_min_max(
+                    Pos2::new(text_pos.x - size.x() / 2.0, text_pos.y - size.y()),
+                    Pos2::new(text_pos.x + size.x() / 2.0, text_pos.y),
+                );
+                if !last_label.is_some_and(|last_label| last_label.intersects(label)) {
+                    handle.text(Overlay(text_pos), Align2::CENTER_TOP, text, font_id, color);
+                    *last_label = Some(label);
+                }
             }
             Y => {
-                let pos_left = Overlay(Pos2 {
-                    x: pos.x - MAYOR_TICK_STROKE_LENGHT / 2.0,
+                //the direction of the outside
+                let outside = if self.mirrored { 1.0 } else { -1.0 };
+                let pos_outside = Overlay(Pos2 {
+                    x: pos.x + outside * outside_length,
                     y: pos.y,
                 });
-                let pos_right = Overlay(Pos2 {
-                    x: pos.x + MAYOR_TICK_STROKE_LENGHT / 2.0,
+                let pos_inside = Overlay(Pos2 {
+                    x: pos.x - outside * inside_length,
                     y: pos.y,
                 });
-                handle.line_segment((pos_left, pos_right), (THICK_LINE_WIDTH, color));
+                handle.line_segment((pos_outside, pos_inside), (THICK_LINE_WIDTH, color));
 
-                let text = Self::print_float(canvas_pos.get_raw_pos().y);
-                let size = handle.text_size(&text, font_id.clone());
-                let text_pos = Overlay(Pos2 {
-                    //subtract the 2.0 for a bit of space between the mayor tick strock and the number text
-                    x: pos.x - size.x() - MAYOR_TICK_STROKE_LENGHT / 2.0 - 2.0,
+                let text = self.format_label(canvas_pos.get_raw_pos().y);
+                let text_pos = Pos2 {
+                    //add the 2.0 for a bit of space between the mayor tick strock and the number text
+                    x: pos.x + outside * (outside_length + 2.0),
                     y: pos.y,
-                });
-                handle.text(text_pos, Align2::LEFT_CENTER, text, font_id, color)
+                };
+                let size = handle.text_size(&text, font_id.clone());
+                let label = Rect::from_two_pos(
+                    Pos2::new(text_pos.x, text_pos.y - size.y() / 2.0),
+                    Pos2::new(text_pos.x + outside * size.x(), text_pos.y + size.y() / 2.0),
+                );
+                let anchor = if self.mirrored {
+                    Align2::LEFT_CENTER
+                } else {
+                    Align2::RIGHT_CENTER
+                };
+                if !last_label.is_some_and(|last_label| last_label.intersects(label)) {
+                    handle.text(Overlay(text_pos), anchor, text, font_id, color);
+                    *last_label = Some(label);
+                }
             }
         }
     }
 
+    fn format_label(&self, value: f32) -> String {
+        match &self.label_formatter {
+            Some(LabelFormatter(formatter)) => formatter(value),
+            None => Self::print_float(value),
+        }
+    }
+
     fn print_float(float: f32) -> String {
         let sign = if float < 0.0 { "-" } else { "" };
         let float = float.abs();
@@ -306,6 +736,7 @@ impl Axis {
                 handle,
                 bounding_box,
                 *axis_section,
+                self.embedded_padding.unwrap_or(DEFAULT_PADDING),
                 kind,
             ),
         }
@@ -347,6 +778,7 @@ impl Axis {
         handle: &CanvasHandle,
         bounding_box: Rectangle,
         axis_section: f32,
+        padding: f32,
         kind: Kind,
     ) -> (Position, Position) {
         use Alignment::{LeftOrBottom, RightOrTop};
@@ -364,7 +796,7 @@ impl Axis {
         let top = right_top.y;
         let right = right_top.x;
 
-        let inner_box = bounding_box.shrink(DEFAULT_PADDING);
+        let inner_box = bounding_box.shrink(padding);
 
         let inner_min = Overlay(inner_box.min().into());
         let inner_max = Overlay(inner_box.max().into());
@@ -382,13 +814,13 @@ impl Axis {
                 if inner_left > axis_section {
                     Axis::get_base_line_points_for_overlay_placement(
                         bounding_box,
-                        LeftOrBottom(DEFAULT_PADDING),
+                        LeftOrBottom(padding),
                         kind,
                     )
                 } else if inner_right < axis_section {
                     Axis::get_base_line_points_for_overlay_placement(
                         bounding_box,
-                        RightOrTop(DEFAULT_PADDING),
+                        RightOrTop(padding),
                         kind,
                     )
                 } else {
@@ -402,13 +834,13 @@ impl Axis {
                 if inner_bottom > axis_section {
                     Axis::get_base_line_points_for_overlay_placement(
                         bounding_box,
-                        LeftOrBottom(DEFAULT_PADDING),
+                        LeftOrBottom(padding),
                         kind,
                     )
                 } else if inner_top < axis_section {
                     Axis::get_base_line_points_for_overlay_placement(
                         bounding_box,
-                        RightOrTop(DEFAULT_PADDING),
+                        RightOrTop(padding),
                         kind,
                     )
                 } else {
@@ -444,6 +876,31 @@ impl Default for Placement {
     }
 }
 
+///the side of the axis line the mayor tick strokes are on
+///outside is the side of the labels (below the x axis and left of the y axis)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TickSide {
+    Inside,
+    Outside,
+    ///the stroke is centered on the axis line
+    #[default]
+    Both,
+}
+
+impl TickSide {
+    ///the length of the stroke (outside, inside) of the axis line in gui pixels
+    fn stroke_lengths(self) -> (f32, f32) {
+        match self {
+            TickSide::Inside => (0.0, MAYOR_TICK_STROKE_LENGHT),
+            TickSide::Outside => (MAYOR_TICK_STROKE_LENGHT, 0.0),
+            TickSide::Both => (
+                MAYOR_TICK_STROKE_LENGHT / 2.0,
+                MAYOR_TICK_STROKE_LENGHT / 2.0,
+            ),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum Alignment {
     ///Left with padding
@@ -453,18 +910,143 @@ pub enum Alignment {
     Center,
 }
 
+#[derive(Debug, Clone, Copy, Default)]
+pub enum LineStyle {
+    #[default]
+    Solid,
+    ///dash_length and gap_length are in gui pixels
+    Dashed { dash_length: f32, gap_length: f32 },
+}
+
+impl LineStyle {
+    fn draw(self, handle: &mut CanvasHandle, points: (Position, Position), stroke: (f32, Color32)) {
+        match self {
+            LineStyle::Solid => handle.line_segment(points, stroke),
+            LineStyle::Dashed {
+                dash_length,
+                gap_length,
+            } => handle.line_segment_dashed(points, stroke, dash_length, gap_length),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum Tick {
     Absolute(f32),
     ///try to print the amount of ticks
     Automatic(u8),
+    ///like Automatic but with nice time intervals (1ms, 1s, 5s, 1min, 1h, 1day, ...)
+    ///one canvas unit is unit long and the canvas value 0.0 is at base
+    ///the ticks are at multiples of the interval since the unix epoch (e.g. at midnight utc)
+    AutomaticTime {
+        wanted_num_ticks: u8,
+        unit: Duration,
+        base: SystemTime,
+    },
 }
 
 impl Tick {
+    ///the positions of the mayor ticks between start and end (in canvas space)
+    ///draw_space is the width or height of the visible canvas region
+    ///pixels_per_unit is the scaling of the axis (see CanvasHandle::pixels_per_canvas_unit)
+    ///the ticks only depend on the arguments so linked axes with the same range get the same ticks
+    pub fn positions(
+        self,
+        start: f32,
+        end: f32,
+        draw_space: f32,
+        pixels_per_unit: f32,
+    ) -> impl Iterator<Item = f32> {
+        let interval = self.get_absolute_tick(draw_space, pixels_per_unit);
+        Tick::positions_with_offset(interval, self.get_offset(interval), start, end)
+    }
+
+    ///the ticks are multiples of the interval so they do not accumulate float errors
+    ///the ticks are yielded lazily so a tiny interval over a huge range does not allocate them all
+    pub(crate) fn positions_with_interval(
+        interval: f32,
+        start: f32,
+        end: f32,
+    ) -> impl Iterator<Item = f32> {
+        Tick::positions_with_offset(interval, 0.0, start, end)
+    }
+
+    ///like positions_with_interval but the ticks are shifted by the offset
+    fn positions_with_offset(
+        interval: f32,
+        offset: f32,
+        start: f32,
+        end: f32,
+    ) -> impl Iterator<Item = f32> {
+        let (first, last) = if interval.is_finite() && interval > 0.0 && offset.is_finite() {
+            (
+                ((start - offset) / interval).ceil() as i64,
+                ((end - offset) / interval).floor() as i64,
+            )
+        } else {
+            //an empty range
+            (1, 0)
+        };
+        (first..=last).map(move |index| offset + index as f32 * interval)
+    }
+
+    ///the canvas value of the tick next to 0.0 in positive direction
+    ///time ticks are aligned to the unix epoch and all other ticks to 0.0
+    fn get_offset(self, interval: f32) -> f32 {
+        match self {
+            Tick::AutomaticTime { unit, base, .. } => {
+                let seconds_per_unit = unit.as_secs_f64();
+                let interval_seconds = interval as f64 * seconds_per_unit;
+                if interval_seconds > 0.0 {
+                    let offset_seconds = (-seconds_since_epoch(base)).rem_euclid(interval_seconds);
+                    (offset_seconds / seconds_per_unit) as f32
+                } else {
+                    0.0
+                }
+            }
+            Tick::Absolute(_) | Tick::Automatic(_) => 0.0,
+        }
+    }
+
     ///get the tick distance
     ///draw_space is the width or height of the axis
     ///depending on the Axis Kind (X or Y)
-    fn get_absolute_tick(self, draw_space: f32) -> f32 {
+    ///automatic ticks get fewer until they are at least MIN_TICK_SPACING pixels apart
+    pub(crate) fn get_absolute_tick(self, draw_space: f32, pixels_per_unit: f32) -> f32 {
+        let mut tick = self;
+        loop {
+            let absolute_tick = tick.get_wanted_absolute_tick(draw_space);
+            match tick.with_fewer_ticks() {
+                Some(fewer_ticks) if absolute_tick * pixels_per_unit.abs() < MIN_TICK_SPACING => {
+                    tick = fewer_ticks
+                }
+                _ => return absolute_tick,
+            }
+        }
+    }
+
+    ///the same tick with one wanted tick less
+    ///None if the number of ticks is fixed or already 1
+    fn with_fewer_ticks(self) -> Option<Tick> {
+        match self {
+            Tick::Absolute(_) => None,
+            Tick::Automatic(wanted_num_ticks) => {
+                (wanted_num_ticks > 1).then(|| Tick::Automatic(wanted_num_ticks - 1))
+            }
+            Tick::AutomaticTime {
+                wanted_num_ticks,
+                unit,
+                base,
+            } => (wanted_num_ticks > 1).then(|| Tick::AutomaticTime {
+                wanted_num_ticks: wanted_num_ticks - 1,
+                unit,
+                base,
+            }),
+        }
+    }
+
+    ///the tick distance for the wanted number of ticks
+    fn get_wanted_absolute_tick(self, draw_space: f32) -> f32 {
         match self {
             Tick::Absolute(tick) => tick,
             Tick::Automatic(wanted_num_ticks) => {
@@ -481,6 +1063,22 @@ impl Tick {
 
                 (best_tick as f64 * tick_shrink_factor) as f32
             }
+            Tick::AutomaticTime {
+                wanted_num_ticks,
+                unit,
+                ..
+            } => {
+                let seconds_per_unit = unit.as_secs_f64();
+                let wanted_interval =
+                    draw_space.abs() as f64 * seconds_per_unit / wanted_num_ticks.max(1) as f64;
+                match nice_time_interval(wanted_interval) {
+                    Some(interval) if seconds_per_unit > 0.0 => {
+                        (interval / seconds_per_unit) as f32
+                    }
+                    //outside of the nice time intervals the decimal ticks are used
+                    _ => Tick::Automatic(wanted_num_ticks).get_wanted_absolute_tick(draw_space),
+                }
+            }
         }
     }
 
@@ -512,3 +1110,143 @@ impl Tick {
         best_tick
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use eframe::emath::pos2;
+
+    use super::*;
+    use crate::painter::estimate_text_size;
+    use crate::{PickRadiusMode, Primitive, RecordingPainter};
+
+    ///the x ticks like Axis::draw computes them for a canvas showing cutout in gui_space
+    fn x_ticks(tick: Tick, gui_space: Rect, mut cutout: Rect) -> Vec<f32> {
+        let mut painter = RecordingPainter::new(gui_space);
+        let handle = CanvasHandle::new(
+            &mut painter,
+            &mut cutout,
+            gui_space,
+            1.0,
+            Default::default(),
+            PickRadiusMode::default(),
+        );
+        let draw_region = handle.get_draw_region_in_canvas_space();
+        tick.positions(
+            draw_region.left(),
+            draw_region.right(),
+            draw_region.width(),
+            handle.pixels_per_canvas_unit().x(),
+        )
+        .collect()
+    }
+
+    #[test]
+    fn linked_cutouts_get_the_same_ticks() {
+        //two stacked plots of the same width that share their cutout
+        let cutout = Rect::from_min_max(pos2(-3.7, 0.0), pos2(96.1, 10.0));
+        let top = Rect::from_min_max(pos2(0.0, 0.0), pos2(400.0, 300.0));
+        let bottom = Rect::from_min_max(pos2(0.0, 300.0), pos2(400.0, 500.0));
+
+        for tick in [
+            Tick::Automatic(5),
+            Tick::Absolute(7.5),
+            Tick::AutomaticTime {
+                wanted_num_ticks: 4,
+                unit: Duration::from_secs(1),
+                base: SystemTime::UNIX_EPOCH + Duration::from_millis(1_650_000_000_250),
+            },
+        ] {
+            let top_ticks = x_ticks(tick, top, cutout);
+            assert!(!top_ticks.is_empty());
+            assert_eq!(top_ticks, x_ticks(tick, bottom, cutout));
+        }
+    }
+
+    #[test]
+    fn automatic_ticks_keep_the_min_spacing_on_a_short_axis() {
+        //a 100 pixel long axis showing 50 canvas units
+        let (draw_space, pixels_per_unit) = (50.0, 2.0);
+        for tick in [
+            Tick::Automatic(5),
+            Tick::Automatic(10),
+            Tick::AutomaticTime {
+                wanted_num_ticks: 10,
+                unit: Duration::from_secs(1),
+                base: SystemTime::UNIX_EPOCH,
+            },
+        ] {
+            let interval = tick.get_absolute_tick(draw_space, pixels_per_unit);
+            assert!(interval * pixels_per_unit >= MIN_TICK_SPACING);
+        }
+    }
+
+    #[test]
+    fn labels_are_thinned_in_a_tiny_draw_region() {
+        let gui_space = Rect::from_min_max(pos2(0.0, 0.0), pos2(120.0, 60.0));
+        let mut cutout = Rect::from_min_max(pos2(0.0, 0.0), pos2(100.0, 10.0));
+        let mut painter = RecordingPainter::new(gui_space);
+        let mut handle = CanvasHandle::new(
+            &mut painter,
+            &mut cutout,
+            gui_space,
+            1.0,
+            Default::default(),
+            PickRadiusMode::default(),
+        );
+        //a tick every canvas unit is less than a pixel apart
+        let mut coordinate_system =
+            CoordinateSystem::<()>::x_axis().with_mayor_tick_interval(Tick::Absolute(1.0));
+        coordinate_system.draw(&mut handle, &());
+
+        let labels: Vec<Rect> = painter
+            .primitives()
+            .iter()
+            .filter_map(|primitive| match primitive {
+                Primitive::Text {
+                    pos,
+                    anchor,
+                    text,
+                    font_id,
+                    ..
+                } => {
+                    let size = estimate_text_size(text, font_id);
+                    Some(anchor.anchor_rect(Rect::from_min_size(*pos, size)))
+                }
+                _ => None,
+            })
+            .collect();
+
+        assert!(!labels.is_empty());
+        assert!(labels.len() < 101);
+        for (label, next_label) in labels.iter().zip(labels.iter().skip(1)) {
+            assert!(!label.intersects(*next_label));
+        }
+    }
+
+    #[test]
+    fn time_ticks_are_aligned_to_the_epoch() {
+        let base_seconds = 1_650_000_000.25;
+        let tick = Tick::AutomaticTime {
+            wanted_num_ticks: 4,
+            unit: Duration::from_secs(1),
+            base: SystemTime::UNIX_EPOCH + Duration::from_secs_f64(base_seconds),
+        };
+        //10 seconds on 1000 pixels
+        let interval = tick.get_absolute_tick(10.0, 100.0);
+        assert_eq!(interval, 2.0);
+        let positions: Vec<f32> = tick.positions(0.0, 10.0, 10.0, 100.0).collect();
+        assert_eq!(positions, [1.75, 3.75, 5.75, 7.75, 9.75]);
+        for position in positions {
+            assert_eq!((base_seconds + position as f64) % interval as f64, 0.0);
+        }
+    }
+
+    #[test]
+    fn tiny_interval_over_a_huge_range_is_not_collected() {
+        //about 2e12 ticks which would not fit into memory
+        let mut positions = Tick::Absolute(0.001).positions(-1e9, 1e9, 2e9, 1e-9);
+        let first = positions.next().unwrap();
+        assert!((first + 1e9).abs() <= 1e3);
+        assert_eq!(positions.take(9).count(), 9);
+    }
+}
@@ -0,0 +1,118 @@
+use std::marker::PhantomData;
+
+use eframe::egui::{Color32, Pos2, Rect, Stroke, Vec2 as GuiVec};
+use eframe::emath::Align2;
+use eframe::epaint::FontId;
+
+use crate::{CanvasHandle, Drawable, Position, Response, OVERLAY_Z_ORDER};
+
+///the distance between the crosshair and its label in gui pixels
+const LABEL_OFFSET: f32 = 6.0;
+
+///guide lines through the cursor labeled with its position in canvas space
+pub struct Crosshair<D> {
+    ///in canvas space None if the cursor is not over the canvas
+    position: Option<Pos2>,
+    ///returns the point (in canvas space) the crosshair snaps to for the cursor position
+    snap: Option<Box<dyn Fn(Pos2) -> Option<Pos2>>>,
+    ///None for the theme color
+    color: Option<Color32>,
+    phantom: PhantomData<D>,
+}
+
+impl<D> Crosshair<D> {
+    pub fn new() -> Crosshair<D> {
+        Crosshair {
+            position: None,
+            snap: None,
+            color: None,
+            phantom: PhantomData,
+        }
+    }
+
+    ///snap to the point the function returns for the cursor position (both in canvas space)
+    ///e.g. the nearest data point
+    ///if it returns None the crosshair stays at the cursor
+    pub fn with_snap(mut self, snap: impl Fn(Pos2) -> Option<Pos2> + 'static) -> Crosshair<D> {
+        self.snap = Some(Box::new(snap));
+        self
+    }
+
+    pub fn with_color(mut self, color: Color32) -> Crosshair<D> {
+        self.color = Some(color);
+        self
+    }
+
+    ///the position of the crosshair in canvas space (after snapping)
+    pub fn position(&self) -> Option<Pos2> {
+        self.position
+    }
+}
+
+impl<D> Default for Crosshair<D> {
+    fn default() -> Self {
+        Crosshair::new()
+    }
+}
+
+impl<D> Drawable for Crosshair<D> {
+    type DrawData = D;
+
+    fn draw(&mut self, _handle: &mut CanvasHandle, _draw_data: &D) {}
+
+    fn draw_overlay(&mut self, handle: &mut CanvasHandle, _draw_data: &D) {
+        let position = match self.position {
+            Some(position) => position,
+            None => return,
+        };
+        let color = self.color.unwrap_or_else(|| handle.foreground_color());
+        let stroke = Stroke::new(1.0, color.linear_multiply(0.5));
+
+        let region = handle.get_draw_region_in_canvas_space();
+        handle.line_segment(
+            (
+                Position::Canvas(Pos2::new(region.left(), position.y)),
+                Position::Canvas(Pos2::new(region.right(), position.y)),
+            ),
+            stroke,
+        );
+        handle.line_segment(
+            (
+                Position::Canvas(Pos2::new(position.x, region.bottom())),
+                Position::Canvas(Pos2::new(position.x, region.top())),
+            ),
+            stroke,
+        );
+
+        let label_pos = handle
+            .convert_to_overlay_space(Position::Canvas(position))
+            .get_raw_pos()
+            + GuiVec::splat(LABEL_OFFSET);
+        handle.text(
+            Position::Overlay(label_pos),
+            Align2::LEFT_BOTTOM,
+            format!("({:.3}, {:.3})", position.x, position.y),
+            FontId::monospace(12.0),
+            color,
+        );
+    }
+
+    fn get_cutout(&mut self, _draw_data: &D) -> Rect {
+        //the crosshair is an overlay so there is no cutout
+        Rect::NOTHING
+    }
+
+    fn handle_input(&mut self, response: &Response, _handle: &CanvasHandle) {
+        self.position = response.canvas_pos.map(|canvas_pos| {
+            let canvas_pos = canvas_pos.get_raw_pos();
+            self.snap
+                .as_ref()
+                .and_then(|snap| snap(canvas_pos))
+                .unwrap_or(canvas_pos)
+        });
+    }
+
+    fn z_order(&self) -> i32 {
+        OVERLAY_Z_ORDER
+    }
+}
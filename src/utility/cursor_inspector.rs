@@ -0,0 +1,171 @@
+use std::marker::PhantomData;
+
+use eframe::egui::{Color32, Pos2, Rect, Stroke, Vec2 as GuiVec};
+use eframe::emath::Align2;
+use eframe::epaint::FontId;
+
+use crate::{CanvasHandle, Drawable, Position, OVERLAY_Z_ORDER};
+
+///the distance between the cursor and the tooltip in gui pixels
+const TOOLTIP_OFFSET: f32 = 12.0;
+
+///the space between the border of the tooltip and the text in gui pixels
+const TOOLTIP_PADDING: f32 = 4.0;
+
+///the y of the line through the points (in canvas space) at x
+///the points have to be sorted by x
+///None if x is outside of the points
+pub fn interpolate_y(points: &[Pos2], x: f32) -> Option<f32> {
+    let first = points.first()?;
+    let last = points.last()?;
+    if !(first.x <= x && x <= last.x) {
+        return None;
+    }
+
+    //the first point right of x (or at x)
+    let index = points.partition_point(|point| point.x < x);
+    let right = points[index];
+    if index == 0 || right.x == x {
+        return Some(right.y);
+    }
+    let left = points[index - 1];
+    let t = (x - left.x) / (right.x - left.x);
+    Some(left.y + t * (right.y - left.y))
+}
+
+///a named line the CursorInspector shows the value of
+pub struct InspectedSeries {
+    pub name: String,
+    pub color: Color32,
+    ///in canvas space sorted by x
+    pub points: Vec<Pos2>,
+}
+
+///a vertical line at the cursor with a tooltip of the y of every series that reaches the cursor x
+pub struct CursorInspector<D> {
+    series: Vec<InspectedSeries>,
+    font_id: FontId,
+    phantom: PhantomData<D>,
+}
+
+impl<D> CursorInspector<D> {
+    pub fn new() -> CursorInspector<D> {
+        CursorInspector {
+            series: Vec::new(),
+            font_id: FontId::monospace(12.0),
+            phantom: PhantomData,
+        }
+    }
+
+    ///the points (in canvas space) have to be sorted by x
+    pub fn with_series(
+        mut self,
+        name: impl Into<String>,
+        color: Color32,
+        points: Vec<Pos2>,
+    ) -> CursorInspector<D> {
+        self.series.push(InspectedSeries {
+            name: name.into(),
+            color,
+            points,
+        });
+        self
+    }
+
+    pub fn with_font_id(mut self, font_id: FontId) -> CursorInspector<D> {
+        self.font_id = font_id;
+        self
+    }
+
+    pub fn series_mut(&mut self) -> &mut Vec<InspectedSeries> {
+        &mut self.series
+    }
+}
+
+impl<D> Default for CursorInspector<D> {
+    fn default() -> Self {
+        CursorInspector::new()
+    }
+}
+
+impl<D> Drawable for CursorInspector<D> {
+    type DrawData = D;
+
+    fn draw(&mut self, _handle: &mut CanvasHandle, _draw_data: &D) {}
+
+    fn draw_overlay(&mut self, handle: &mut CanvasHandle, _draw_data: &D) {
+        let (gui_pos, _overlay_pos, canvas_pos) = match handle.cursor_positions() {
+            Some(positions) => positions,
+            None => return,
+        };
+        let gui_pos = gui_pos.get_raw_pos();
+        let x = canvas_pos.get_raw_pos().x;
+        let foreground_color = handle.foreground_color();
+
+        //the vertical line over the whole canvas
+        let region = handle.get_draw_region_in_canvas_space();
+        handle.line_segment(
+            (
+                Position::Canvas(Pos2::new(x, region.bottom())),
+                Position::Canvas(Pos2::new(x, region.top())),
+            ),
+            Stroke::new(1.0, foreground_color.linear_multiply(0.5)),
+        );
+
+        let lines: Vec<(String, Color32)> = self
+            .series
+            .iter()
+            .filter_map(|series| {
+                interpolate_y(&series.points, x)
+                    .map(|y| (format!("{}: {:.3}", series.name, y), series.color))
+            })
+            .collect();
+        if lines.is_empty() {
+            return;
+        }
+
+        let line_sizes: Vec<GuiVec> = lines
+            .iter()
+            .map(|(line, _color)| handle.text_size(line, self.font_id.clone()).into())
+            .collect();
+        let width = line_sizes.iter().map(|size| size.x).fold(0.0, f32::max);
+        let height: f32 = line_sizes.iter().map(|size| size.y).sum();
+        let tooltip = Rect::from_min_size(
+            gui_pos + GuiVec::splat(TOOLTIP_OFFSET),
+            GuiVec::new(width, height) + GuiVec::splat(2.0 * TOOLTIP_PADDING),
+        );
+
+        let background_color = if handle.dark_mode() {
+            Color32::from_black_alpha(200)
+        } else {
+            Color32::from_white_alpha(220)
+        };
+        handle.rect(
+            Position::Gui(tooltip.min),
+            Position::Gui(tooltip.max),
+            2.0,
+            background_color,
+            Stroke::new(1.0, foreground_color),
+        );
+        let mut line_pos = tooltip.min + GuiVec::splat(TOOLTIP_PADDING);
+        for ((line, color), size) in lines.into_iter().zip(line_sizes) {
+            handle.text(
+                Position::Gui(line_pos),
+                Align2::LEFT_TOP,
+                line,
+                self.font_id.clone(),
+                color,
+            );
+            line_pos.y += size.y;
+        }
+    }
+
+    fn get_cutout(&mut self, _draw_data: &D) -> Rect {
+        //the inspector is an overlay so there is no cutout
+        Rect::NOTHING
+    }
+
+    fn z_order(&self) -> i32 {
+        OVERLAY_Z_ORDER
+    }
+}
@@ -0,0 +1,139 @@
+use eframe::emath::{Pos2, Rect};
+use simple_math::{Rectangle, Vec2};
+
+use crate::canvas_handle::clip_segment;
+use crate::Position;
+
+///reduces the line through the points (in canvas space) to the runs that are worth drawing
+///segments that miss the region are culled and split the line into separate runs
+///so a line that leaves the region and comes back does not get a false chord
+///the points at the ends of a run may be outside of the region so the line runs to its edge
+///points closer than one pixel to the last kept point are merged into it
+///the order of the points is kept so every run can be drawn as a line
+///
+///this is an opt-in tool for Drawables with many points, use it in Drawable::draw e.g. with
+///`decimate(points, handle.pixels_per_canvas_unit(), handle.visible_canvas_rect())`
+///and draw every run with handle.polyline
+pub fn decimate(
+    points: impl IntoIterator<Item = Pos2>,
+    pixels_per_unit: Vec2,
+    region: Rectangle,
+) -> Vec<Vec<Position>> {
+    let region = Rect::from_two_pos(
+        Pos2::new(region.left(), region.bottom()),
+        Pos2::new(region.right(), region.top()),
+    );
+
+    let mut runs = Vec::new();
+    let mut run: Vec<Position> = Vec::new();
+    //the last merged point which still has to end the run
+    let mut merged = None;
+    let mut previous: Option<Pos2> = None;
+    for point in points {
+        let visible_segment =
+            previous.filter(|previous| clip_segment([*previous, point], region).is_some());
+        match visible_segment {
+            Some(previous) => {
+                if run.is_empty() {
+                    run.push(Position::Canvas(previous));
+                }
+                let last = run[run.len() - 1].get_raw_pos();
+                let pixel_distance_x = ((point.x - last.x) * pixels_per_unit.x()).abs();
+                let pixel_distance_y = ((point.y - last.y) * pixels_per_unit.y()).abs();
+                if pixel_distance_x < 1.0 && pixel_distance_y < 1.0 {
+                    merged = Some(point);
+                } else {
+                    run.push(Position::Canvas(point));
+                    merged = None;
+                }
+            }
+            None => end_run(&mut runs, &mut run, &mut merged),
+        }
+        previous = Some(point);
+    }
+    end_run(&mut runs, &mut run, &mut merged);
+    runs
+}
+
+fn end_run(runs: &mut Vec<Vec<Position>>, run: &mut Vec<Position>, merged: &mut Option<Pos2>) {
+    if let Some(merged) = merged.take() {
+        run.push(Position::Canvas(merged));
+    }
+    if !run.is_empty() {
+        runs.push(std::mem::take(run));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line() -> Vec<Pos2> {
+        (0..=100).map(|x| Pos2::new(x as f32, 0.0)).collect()
+    }
+
+    fn xs(run: &[Position]) -> Vec<f32> {
+        run.iter().map(|pos| pos.get_raw_pos().x).collect()
+    }
+
+    #[test]
+    fn culls_segments_outside_of_the_region() {
+        let region = Rectangle::new(Vec2::new(20.5, -1.0), Vec2::new(40.5, 1.0));
+        let runs = decimate(line(), Vec2::new(10.0, 10.0), region);
+        //the neighbours outside of the region are kept so the line runs to the edge
+        let expected: Vec<f32> = (20..=41).map(|x| x as f32).collect();
+        assert_eq!(runs.len(), 1);
+        assert_eq!(xs(&runs[0]), expected);
+    }
+
+    #[test]
+    fn keeps_every_point_when_zoomed_in() {
+        let region = Rectangle::new(Vec2::new(0.0, -1.0), Vec2::new(100.0, 1.0));
+        let runs = decimate(line(), Vec2::new(2.0, 2.0), region);
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].len(), 101);
+    }
+
+    #[test]
+    fn merges_points_closer_than_a_pixel_when_zoomed_out() {
+        let region = Rectangle::new(Vec2::new(0.0, -1.0), Vec2::new(100.0, 1.0));
+        //4 units per pixel so only every 4th point is a pixel away from the last kept one
+        let runs = decimate(line(), Vec2::new(0.25, 0.25), region);
+        let expected: Vec<f32> = (0..=100).step_by(4).map(|x| x as f32).collect();
+        assert_eq!(xs(&runs[0]), expected);
+    }
+
+    #[test]
+    fn keeps_the_segment_through_a_deep_zoom() {
+        let region = Rectangle::new(Vec2::new(50.25, -0.1), Vec2::new(50.75, 0.1));
+        let runs = decimate(line(), Vec2::new(1000.0, 1000.0), region);
+        assert_eq!(runs.len(), 1);
+        assert_eq!(xs(&runs[0]), [50.0, 51.0]);
+    }
+
+    #[test]
+    fn line_crossing_the_edge_runs_to_the_outside_point() {
+        let region = Rectangle::new(Vec2::new(5.0, -1.0), Vec2::new(20.0, 1.0));
+        let points = [Pos2::new(0.0, 0.0), Pos2::new(10.0, 0.0)];
+        let runs = decimate(points, Vec2::new(1.0, 1.0), region);
+        assert_eq!(runs.len(), 1);
+        assert_eq!(xs(&runs[0]), [0.0, 10.0]);
+    }
+
+    #[test]
+    fn line_that_exits_and_reenters_is_split() {
+        let region = Rectangle::new(Vec2::new(-1.0, -1.0), Vec2::new(6.0, 1.0));
+        let points = [
+            Pos2::new(0.0, 0.0),
+            Pos2::new(1.0, 0.0),
+            Pos2::new(2.0, 100.0),
+            Pos2::new(3.0, 100.0),
+            Pos2::new(4.0, 0.0),
+            Pos2::new(5.0, 0.0),
+        ];
+        let runs = decimate(points, Vec2::new(1.0, 1.0), region);
+        assert_eq!(runs.len(), 2);
+        assert_eq!(xs(&runs[0]), [0.0, 1.0, 2.0]);
+        assert_eq!(xs(&runs[1]), [3.0, 4.0, 5.0]);
+    }
+}
@@ -0,0 +1,157 @@
+use std::marker::PhantomData;
+
+use eframe::egui::{Color32, Pos2, Rect, Stroke, Vec2 as GuiVec};
+use eframe::emath::Align2;
+use eframe::epaint::FontId;
+
+use crate::{CanvasHandle, Drawable, Position, OVERLAY_Z_ORDER};
+
+///the space between the border of the box and the entries in gui pixels
+const PADDING: f32 = 6.0;
+
+///the space between the swatch and the label and between the rows in gui pixels
+const SPACING: f32 = 4.0;
+
+///a box in a corner of the canvas with a colored swatch and a label for every series
+pub struct Legend<D> {
+    entries: Vec<(String, Color32)>,
+    ///the corner (or edge) of the canvas the box is placed in
+    anchor: Align2,
+    ///the distance to the border of the canvas in gui pixels
+    margin: f32,
+    font_id: FontId,
+    phantom: PhantomData<D>,
+}
+
+impl<D> Legend<D> {
+    ///the entries are drawn from top to bottom
+    ///default is the top right corner with a margin of 10.0
+    pub fn new(entries: impl IntoIterator<Item = (impl ToString, Color32)>) -> Legend<D> {
+        Legend {
+            entries: entries
+                .into_iter()
+                .map(|(label, color)| (label.to_string(), color))
+                .collect(),
+            anchor: Align2::RIGHT_TOP,
+            margin: 10.0,
+            font_id: FontId::proportional(12.0),
+            phantom: PhantomData,
+        }
+    }
+
+    pub fn with_anchor(mut self, anchor: Align2) -> Legend<D> {
+        self.anchor = anchor;
+        self
+    }
+
+    pub fn with_margin(mut self, margin: f32) -> Legend<D> {
+        self.margin = margin;
+        self
+    }
+
+    pub fn with_font(mut self, font_id: FontId) -> Legend<D> {
+        self.font_id = font_id;
+        self
+    }
+}
+
+impl<D> Drawable for Legend<D> {
+    type DrawData = D;
+
+    ///drawn with the data (and not as draw_overlay) so the exports include the legend
+    fn draw(&mut self, handle: &mut CanvasHandle, _draw_data: &D) {
+        if self.entries.is_empty() {
+            return;
+        }
+
+        let row_height = self.font_id.size;
+        let label_width = self
+            .entries
+            .iter()
+            .map(|(label, _color)| handle.text_size(label, self.font_id.clone()).x())
+            .fold(0.0, f32::max);
+        let rows = self.entries.len() as f32;
+        let size = GuiVec::new(
+            2.0 * PADDING + row_height + SPACING + label_width,
+            2.0 * PADDING + rows * row_height + (rows - 1.0) * SPACING,
+        );
+
+        let bounding_box = handle.bounding_box();
+        let canvas_rect = Rect::from_two_pos(bounding_box.min().into(), bounding_box.max().into());
+        let legend_rect = self
+            .anchor
+            .align_size_within_rect(size, canvas_rect.shrink(self.margin));
+
+        let foreground_color = handle.foreground_color();
+        let background_color = if handle.dark_mode() {
+            Color32::from_black_alpha(200)
+        } else {
+            Color32::from_white_alpha(200)
+        };
+        handle.rect(
+            Position::Gui(legend_rect.min),
+            Position::Gui(legend_rect.max),
+            2.0,
+            background_color,
+            Stroke::new(1.0, foreground_color.linear_multiply(0.5)),
+        );
+
+        for (index, (label, color)) in self.entries.iter().enumerate() {
+            let row_min = legend_rect.min
+                + GuiVec::new(PADDING, PADDING + index as f32 * (row_height + SPACING));
+            let swatch = Rect::from_min_size(row_min, GuiVec::splat(row_height)).shrink(2.0);
+            handle.rect(
+                Position::Gui(swatch.min),
+                Position::Gui(swatch.max),
+                1.0,
+                *color,
+                Stroke::none(),
+            );
+            handle.text(
+                Position::Gui(Pos2::new(
+                    row_min.x + row_height + SPACING,
+                    row_min.y + row_height / 2.0,
+                )),
+                Align2::LEFT_CENTER,
+                label,
+                self.font_id.clone(),
+                foreground_color,
+            );
+        }
+    }
+
+    fn get_cutout(&mut self, _draw_data: &D) -> Rect {
+        //the legend is an overlay so there is no cutout
+        Rect::NOTHING
+    }
+
+    fn z_order(&self) -> i32 {
+        OVERLAY_Z_ORDER
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use eframe::emath::pos2;
+
+    use super::*;
+    use crate::{CanvasState, Primitive, RecordingPainter};
+
+    #[test]
+    fn legend_is_part_of_the_exported_drawing() {
+        let gui_space = Rect::from_min_max(pos2(0.0, 0.0), pos2(300.0, 200.0));
+        let mut painter = RecordingPainter::new(gui_space);
+        let mut legend = Legend::<()>::new([("measured", Color32::RED), ("fit", Color32::BLUE)]);
+        CanvasState::new().draw_with_painter(&mut legend, &(), gui_space, &mut painter);
+
+        let labels: Vec<&str> = painter
+            .primitives()
+            .iter()
+            .filter_map(|primitive| match primitive {
+                Primitive::Text { text, .. } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(labels, ["measured", "fit"]);
+    }
+}
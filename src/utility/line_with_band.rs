@@ -0,0 +1,124 @@
+use std::marker::PhantomData;
+
+use eframe::emath::{Pos2, Rect};
+use eframe::epaint::{Color32, Stroke};
+
+use crate::{CanvasHandle, Drawable, Position};
+
+///a line with a shaded band around it e.g. mean ± standard deviation
+///points with a NaN coordinate or magnitude are gaps which split the line and the band
+pub struct LineWithBand<D> {
+    ///in canvas space
+    center: Vec<Pos2>,
+    ///the extend of the band below the center
+    minus: Vec<f32>,
+    ///the extend of the band above the center
+    plus: Vec<f32>,
+    line_stroke: Stroke,
+    band_color: Color32,
+    phantom: PhantomData<D>,
+}
+
+impl<D> LineWithBand<D> {
+    ///the band goes from y - magnitude to y + magnitude
+    pub fn new(center: Vec<Pos2>, magnitudes: Vec<f32>) -> LineWithBand<D> {
+        LineWithBand::new_asymmetric(center, magnitudes.clone(), magnitudes)
+    }
+
+    ///the band goes from y - minus to y + plus
+    pub fn new_asymmetric(center: Vec<Pos2>, minus: Vec<f32>, plus: Vec<f32>) -> LineWithBand<D> {
+        LineWithBand {
+            center,
+            minus,
+            plus,
+            line_stroke: Stroke::new(1.5, Color32::LIGHT_BLUE),
+            band_color: Color32::from_rgba_unmultiplied(100, 150, 255, 60),
+            phantom: PhantomData,
+        }
+    }
+
+    pub fn with_line_stroke(mut self, line_stroke: impl Into<Stroke>) -> LineWithBand<D> {
+        self.line_stroke = line_stroke.into();
+        self
+    }
+
+    pub fn with_band_color(mut self, band_color: impl Into<Color32>) -> LineWithBand<D> {
+        self.band_color = band_color.into();
+        self
+    }
+
+    ///the center, the bottom and the top of the band of every point
+    ///None for gaps
+    fn band_points(&self) -> impl Iterator<Item = Option<(Pos2, f32, f32)>> + '_ {
+        self.center
+            .iter()
+            .zip(self.minus.iter().zip(self.plus.iter()))
+            .map(|(center, (minus, plus))| {
+                let bottom = center.y - minus;
+                let top = center.y + plus;
+                (center.x.is_finite() && bottom.is_finite() && top.is_finite())
+                    .then_some((*center, bottom, top))
+            })
+    }
+
+    ///the runs of consecutive points without gaps
+    fn segments(&self) -> Vec<Vec<(Pos2, f32, f32)>> {
+        let mut segments = vec![Vec::new()];
+        for band_point in self.band_points() {
+            match band_point {
+                Some(band_point) => segments.last_mut().unwrap().push(band_point),
+                None if segments.last().is_some_and(|segment| !segment.is_empty()) => {
+                    segments.push(Vec::new())
+                }
+                None => {}
+            }
+        }
+        segments.retain(|segment| !segment.is_empty());
+        segments
+    }
+}
+
+impl<D> Drawable for LineWithBand<D> {
+    type DrawData = D;
+
+    fn draw(&mut self, handle: &mut CanvasHandle, _draw_data: &D) {
+        use Position::Canvas;
+        for segment in self.segments() {
+            //the band is in general not convex so it is drawn as one quad per line piece
+            for window in segment.windows(2) {
+                let (a, a_bottom, a_top) = window[0];
+                let (b, b_bottom, b_top) = window[1];
+                let quad = [
+                    Canvas((a.x, a_bottom).into()),
+                    Canvas((b.x, b_bottom).into()),
+                    Canvas((b.x, b_top).into()),
+                    Canvas((a.x, a_top).into()),
+                ];
+                handle.polygon(quad, self.band_color, Stroke::none());
+            }
+
+            //the line is drawn on top of the band
+            let line = segment.iter().map(|(center, _, _)| Canvas(*center));
+            handle.polyline(line, self.line_stroke);
+        }
+    }
+
+    fn get_cutout(&mut self, _draw_data: &D) -> Rect {
+        let cutout =
+            self.band_points()
+                .flatten()
+                .fold(Rect::NOTHING, |cutout, (center, bottom, top)| {
+                    cutout.union(Rect::from_two_pos(
+                        (center.x, bottom).into(),
+                        (center.x, top).into(),
+                    ))
+                });
+
+        if cutout.is_negative() {
+            //dummy value
+            Rect::from_two_pos((0.0, 0.0).into(), (10.0, 10.0).into())
+        } else {
+            cutout
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use std::collections::VecDeque;
+use std::marker::PhantomData;
+
+use eframe::egui::{Pos2, Rect, Vec2 as GuiVec};
+use eframe::emath::Align2;
+use eframe::epaint::FontId;
+
+use crate::{CanvasHandle, Drawable, Position, OVERLAY_Z_ORDER};
+
+///the number of frames the frame time is averaged over
+const DEFAULT_WINDOW: usize = 30;
+
+///the averaged frames per second and frame time in the top right corner
+pub struct PerfOverlay<D> {
+    last_time: Option<f64>,
+    ///in seconds
+    frame_times: VecDeque<f64>,
+    window: usize,
+    phantom: PhantomData<D>,
+}
+
+impl<D> PerfOverlay<D> {
+    pub fn new() -> PerfOverlay<D> {
+        PerfOverlay {
+            last_time: None,
+            frame_times: VecDeque::with_capacity(DEFAULT_WINDOW),
+            window: DEFAULT_WINDOW,
+            phantom: PhantomData,
+        }
+    }
+
+    ///the number of frames the frame time is averaged over
+    ///default is 30
+    pub fn with_window(mut self, window: usize) -> PerfOverlay<D> {
+        self.window = window.max(1);
+        self
+    }
+
+    ///the average frame time in seconds
+    ///None until two frames were drawn
+    pub fn frame_time(&self) -> Option<f64> {
+        if self.frame_times.is_empty() {
+            None
+        } else {
+            Some(self.frame_times.iter().sum::<f64>() / self.frame_times.len() as f64)
+        }
+    }
+
+    fn record_frame(&mut self, time: f64) {
+        if let Some(last_time) = self.last_time {
+            let frame_time = time - last_time;
+            //drawing twice in one frame is not a new frame
+            if frame_time > 0.0 {
+                self.frame_times.push_back(frame_time);
+                while self.frame_times.len() > self.window {
+                    self.frame_times.pop_front();
+                }
+            }
+        }
+        self.last_time = Some(time);
+    }
+}
+
+impl<D> Default for PerfOverlay<D> {
+    fn default() -> Self {
+        PerfOverlay::new()
+    }
+}
+
+impl<D> Drawable for PerfOverlay<D> {
+    type DrawData = D;
+
+    fn draw(&mut self, _handle: &mut CanvasHandle, _draw_data: &D) {}
+
+    fn draw_overlay(&mut self, handle: &mut CanvasHandle, _draw_data: &D) {
+        self.record_frame(handle.time());
+        let text = match self.frame_time() {
+            Some(frame_time) => format!(
+                "{:.0} fps ({:.1} ms)",
+                1.0 / frame_time,
+                frame_time * 1000.0
+            ),
+            None => "- fps".to_string(),
+        };
+
+        let region = handle.get_draw_region_in_canvas_space();
+        let corner = Pos2::new(
+            region.left().max(region.right()),
+            region.bottom().max(region.top()),
+        );
+        let corner = handle.convert_to_overlay_space(Position::Canvas(corner));
+        let pos = Position::Overlay(corner.get_raw_pos() - GuiVec::new(4.0, 4.0));
+        handle.text(
+            pos,
+            Align2::RIGHT_TOP,
+            text,
+            FontId::monospace(12.0),
+            handle.foreground_color(),
+        );
+    }
+
+    fn get_cutout(&mut self, _draw_data: &D) -> Rect {
+        //the overlay has no cutout
+        Rect::NOTHING
+    }
+
+    fn z_order(&self) -> i32 {
+        OVERLAY_Z_ORDER
+    }
+}
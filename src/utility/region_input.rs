@@ -0,0 +1,147 @@
+use std::ops::Range;
+
+use eframe::emath::Rect;
+
+use crate::{CanvasHandle, Drawable, Response};
+
+///only forwards the input to the drawable if the cursor is inside of the region (in canvas space)
+///drawing is always forwarded
+#[derive(Debug, Clone)]
+pub struct RegionInput<T> {
+    drawable: T,
+    region: Rect,
+}
+
+impl<T> RegionInput<T> {
+    pub fn new(drawable: T, region: Rect) -> RegionInput<T> {
+        RegionInput { drawable, region }
+    }
+
+    pub fn set_region(&mut self, region: Rect) {
+        self.region = region;
+    }
+
+    pub fn inner(&self) -> &T {
+        &self.drawable
+    }
+
+    pub fn inner_mut(&mut self) -> &mut T {
+        &mut self.drawable
+    }
+
+    pub fn into_inner(self) -> T {
+        self.drawable
+    }
+}
+
+impl<T, D> Drawable for RegionInput<T>
+where
+    T: Drawable<DrawData = D>,
+{
+    type DrawData = D;
+
+    fn draw(&mut self, handle: &mut CanvasHandle, draw_data: &Self::DrawData) {
+        self.drawable.draw(handle, draw_data);
+    }
+
+    fn draw_range(
+        &mut self,
+        handle: &mut CanvasHandle,
+        draw_data: &Self::DrawData,
+        range: Range<f32>,
+    ) {
+        self.drawable.draw_range(handle, draw_data, range);
+    }
+
+    fn draw_overlay(&mut self, handle: &mut CanvasHandle, draw_data: &Self::DrawData) {
+        self.drawable.draw_overlay(handle, draw_data);
+    }
+
+    fn get_cutout(&mut self, draw_data: &Self::DrawData) -> Rect {
+        self.drawable.get_cutout(draw_data)
+    }
+
+    fn handle_input(&mut self, response: &Response, handle: &CanvasHandle) {
+        let cursor_in_region = response.curser_pos.is_some_and(|curser_pos| {
+            let curser_pos = handle.convert_to_canvas_space(curser_pos).get_raw_pos();
+            self.region.contains(curser_pos)
+        });
+        if cursor_in_region {
+            self.drawable.handle_input(response, handle);
+        }
+    }
+
+    fn z_order(&self) -> i32 {
+        self.drawable.z_order()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use eframe::emath::{pos2, Pos2};
+    use simple_math::Vec2;
+
+    use super::*;
+    use crate::{PickRadiusMode, Position, RecordingPainter};
+
+    ///counts how often the input is forwarded
+    struct InputCounter(usize);
+
+    impl Drawable for InputCounter {
+        type DrawData = ();
+
+        fn draw(&mut self, _handle: &mut CanvasHandle, _draw_data: &()) {}
+
+        fn get_cutout(&mut self, _draw_data: &()) -> Rect {
+            Rect::NOTHING
+        }
+
+        fn handle_input(&mut self, _response: &Response, _handle: &CanvasHandle) {
+            self.0 += 1;
+        }
+    }
+
+    fn click_at(pos: Pos2) -> Response {
+        Response {
+            curser_pos: Some(Position::Canvas(pos)),
+            clicked: true,
+            canvas_pos: Some(Position::Canvas(pos)),
+            dragged: false,
+            drag_delta_canvas: Vec2::new(0.0, 0.0),
+            scroll_delta: 0.0,
+            selection: None,
+            selection_committed: false,
+            secondary_clicked: false,
+            secondary_click_pos: None,
+        }
+    }
+
+    fn forwarded_clicks(pos: Pos2) -> usize {
+        let gui_space = Rect::from_min_max(pos2(0.0, 0.0), pos2(100.0, 100.0));
+        let mut painter = RecordingPainter::new(gui_space);
+        let mut cutout = Rect::from_min_max(pos2(0.0, 0.0), pos2(10.0, 10.0));
+        let handle = CanvasHandle::new(
+            &mut painter,
+            &mut cutout,
+            gui_space,
+            1.0,
+            Default::default(),
+            PickRadiusMode::default(),
+        );
+
+        let region = Rect::from_min_max(pos2(0.0, 0.0), pos2(5.0, 5.0));
+        let mut region_input = RegionInput::new(InputCounter(0), region);
+        region_input.handle_input(&click_at(pos), &handle);
+        region_input.inner().0
+    }
+
+    #[test]
+    fn click_inside_of_the_region_is_forwarded() {
+        assert_eq!(forwarded_clicks(pos2(2.0, 3.0)), 1);
+    }
+
+    #[test]
+    fn click_outside_of_the_region_is_not_forwarded() {
+        assert_eq!(forwarded_clicks(pos2(7.0, 3.0)), 0);
+    }
+}
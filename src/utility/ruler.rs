@@ -0,0 +1,125 @@
+use std::marker::PhantomData;
+
+use eframe::egui::{Color32, Pos2, Rect, Stroke, Vec2 as GuiVec};
+use eframe::emath::Align2;
+use eframe::epaint::FontId;
+
+use crate::{CanvasHandle, Drawable, Position, Response, OVERLAY_Z_ORDER};
+
+///the radius of the markers at the measured points in gui pixels
+const MARKER_RADIUS: f32 = 3.0;
+
+///the distance between the line and its label in gui pixels
+const LABEL_OFFSET: f32 = 6.0;
+
+///measures the euclidean distance between two clicked points and the third click clears it
+pub struct Ruler<D> {
+    ///the clicked points in canvas space
+    start: Option<Pos2>,
+    end: Option<Pos2>,
+    ///in canvas space to preview the line while only the start is set
+    hovered: Option<Pos2>,
+    ///None for the theme color
+    color: Option<Color32>,
+    phantom: PhantomData<D>,
+}
+
+impl<D> Ruler<D> {
+    pub fn new() -> Ruler<D> {
+        Ruler {
+            start: None,
+            end: None,
+            hovered: None,
+            color: None,
+            phantom: PhantomData,
+        }
+    }
+
+    pub fn with_color(mut self, color: Color32) -> Ruler<D> {
+        self.color = Some(color);
+        self
+    }
+
+    ///the distance between the two clicked points in canvas units
+    ///None until both points are clicked
+    pub fn distance(&self) -> Option<f32> {
+        Some(self.start?.distance(self.end?))
+    }
+
+    ///the two clicked points in canvas space
+    pub fn points(&self) -> Option<(Pos2, Pos2)> {
+        Some((self.start?, self.end?))
+    }
+
+    pub fn clear(&mut self) {
+        self.start = None;
+        self.end = None;
+    }
+}
+
+impl<D> Default for Ruler<D> {
+    fn default() -> Self {
+        Ruler::new()
+    }
+}
+
+impl<D> Drawable for Ruler<D> {
+    type DrawData = D;
+
+    fn draw(&mut self, _handle: &mut CanvasHandle, _draw_data: &D) {}
+
+    fn draw_overlay(&mut self, handle: &mut CanvasHandle, _draw_data: &D) {
+        let start = match self.start {
+            Some(start) => start,
+            None => return,
+        };
+        let color = self.color.unwrap_or_else(|| handle.foreground_color());
+
+        handle.circle_filled(Position::Canvas(start), MARKER_RADIUS, color);
+        let end = match self.end.or(self.hovered) {
+            Some(end) => end,
+            None => return,
+        };
+        if self.end.is_some() {
+            handle.circle_filled(Position::Canvas(end), MARKER_RADIUS, color);
+        }
+        handle.line_segment(
+            (Position::Canvas(start), Position::Canvas(end)),
+            Stroke::new(1.5, color),
+        );
+
+        let label_pos = handle
+            .convert_to_overlay_space(Position::Canvas(start + (end - start) / 2.0))
+            .get_raw_pos()
+            + GuiVec::splat(LABEL_OFFSET);
+        handle.text(
+            Position::Overlay(label_pos),
+            Align2::LEFT_BOTTOM,
+            format!("{:.3}", start.distance(end)),
+            FontId::monospace(12.0),
+            color,
+        );
+    }
+
+    fn get_cutout(&mut self, _draw_data: &D) -> Rect {
+        //the ruler is an overlay so there is no cutout
+        Rect::NOTHING
+    }
+
+    fn handle_input(&mut self, response: &Response, _handle: &CanvasHandle) {
+        self.hovered = response.canvas_pos.map(Position::get_raw_pos);
+        if !response.clicked {
+            return;
+        }
+        match (self.start, self.end, self.hovered) {
+            (None, _, Some(clicked)) => self.start = Some(clicked),
+            (Some(_), None, Some(clicked)) => self.end = Some(clicked),
+            (Some(_), Some(_), _) => self.clear(),
+            _ => {}
+        }
+    }
+
+    fn z_order(&self) -> i32 {
+        OVERLAY_Z_ORDER
+    }
+}
@@ -0,0 +1,115 @@
+use std::marker::PhantomData;
+
+use eframe::egui::{Color32, Pos2, Rect, Stroke, Vec2 as GuiVec};
+use eframe::emath::Align2;
+use eframe::epaint::FontId;
+
+use crate::{CanvasHandle, Drawable, Position, Tick, OVERLAY_Z_ORDER};
+
+///the number of grid lines along the x axis that are aimed for
+const DEFAULT_NUMBER_OF_LINES: u8 = 10;
+
+///faint grid lines at round canvas unit spacings
+///the spacing is labeled in the bottom left corner (e.g. "grid: 10 m")
+///the same spacing is used for both axes so the cells are square in canvas units
+pub struct RulerGrid<D> {
+    ///appended to the spacing in the label
+    unit: String,
+    wanted_num_lines: u8,
+    phantom: PhantomData<D>,
+}
+
+impl<D> RulerGrid<D> {
+    pub fn new() -> RulerGrid<D> {
+        RulerGrid {
+            unit: String::new(),
+            wanted_num_lines: DEFAULT_NUMBER_OF_LINES,
+            phantom: PhantomData,
+        }
+    }
+
+    ///the unit of the canvas space (e.g. "m")
+    pub fn with_unit(mut self, unit: impl Into<String>) -> RulerGrid<D> {
+        self.unit = unit.into();
+        self
+    }
+
+    ///the number of grid lines along the x axis that are aimed for
+    ///there are fewer lines if they would get too close
+    pub fn with_wanted_num_lines(mut self, wanted_num_lines: u8) -> RulerGrid<D> {
+        self.wanted_num_lines = wanted_num_lines;
+        self
+    }
+
+    fn label(&self, spacing: f32) -> String {
+        if self.unit.is_empty() {
+            format!("grid: {}", spacing)
+        } else {
+            format!("grid: {} {}", spacing, self.unit)
+        }
+    }
+}
+
+impl<D> Default for RulerGrid<D> {
+    fn default() -> Self {
+        RulerGrid::new()
+    }
+}
+
+impl<D> Drawable for RulerGrid<D> {
+    type DrawData = D;
+
+    fn draw(&mut self, handle: &mut CanvasHandle, _draw_data: &D) {
+        let color = if handle.dark_mode() {
+            Color32::from_white_alpha(40)
+        } else {
+            Color32::from_black_alpha(40)
+        };
+        let stroke = Stroke::new(1.0, color);
+
+        let region = handle.get_draw_region_in_canvas_space();
+        let pixels_per_unit = handle.pixels_per_canvas_unit();
+        let spacing = Tick::Automatic(self.wanted_num_lines)
+            .get_absolute_tick(region.width(), pixels_per_unit.x().min(pixels_per_unit.y()));
+
+        let (left, right) = (
+            region.left().min(region.right()),
+            region.left().max(region.right()),
+        );
+        let (bottom, top) = (
+            region.bottom().min(region.top()),
+            region.bottom().max(region.top()),
+        );
+        for x in Tick::positions_with_interval(spacing, left, right) {
+            let from = Position::Canvas(Pos2::new(x, bottom));
+            let to = Position::Canvas(Pos2::new(x, top));
+            handle.line_segment((from, to), stroke);
+        }
+        for y in Tick::positions_with_interval(spacing, bottom, top) {
+            let from = Position::Canvas(Pos2::new(left, y));
+            let to = Position::Canvas(Pos2::new(right, y));
+            handle.line_segment((from, to), stroke);
+        }
+
+        if spacing.is_finite() && spacing > 0.0 {
+            let corner = handle.convert_to_overlay_space(Position::Canvas(Pos2::new(left, bottom)));
+            let label_pos = Position::Overlay(corner.get_raw_pos() + GuiVec::new(4.0, 4.0));
+            handle.text(
+                label_pos,
+                Align2::LEFT_BOTTOM,
+                self.label(spacing),
+                FontId::default(),
+                handle.foreground_color(),
+            );
+        }
+    }
+
+    fn get_cutout(&mut self, _draw_data: &D) -> Rect {
+        //the grid is an overlay so there is no cutout
+        Rect::NOTHING
+    }
+
+    fn z_order(&self) -> i32 {
+        OVERLAY_Z_ORDER
+    }
+}
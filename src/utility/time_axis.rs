@@ -0,0 +1,172 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const MINUTE: f64 = 60.0;
+const HOUR: f64 = 60.0 * MINUTE;
+const DAY: f64 = 24.0 * HOUR;
+
+///the nice tick intervals in seconds
+const TIME_TICKS: [f64; 31] = [
+    0.001,
+    0.002,
+    0.005,
+    0.01,
+    0.02,
+    0.05,
+    0.1,
+    0.2,
+    0.5,
+    1.0,
+    2.0,
+    5.0,
+    10.0,
+    15.0,
+    30.0,
+    MINUTE,
+    2.0 * MINUTE,
+    5.0 * MINUTE,
+    10.0 * MINUTE,
+    15.0 * MINUTE,
+    30.0 * MINUTE,
+    HOUR,
+    2.0 * HOUR,
+    3.0 * HOUR,
+    6.0 * HOUR,
+    12.0 * HOUR,
+    DAY,
+    2.0 * DAY,
+    7.0 * DAY,
+    14.0 * DAY,
+    28.0 * DAY,
+];
+
+///the nice time interval (in seconds) that is closest to the wanted interval
+///None if the wanted interval is outside of the nice time intervals
+pub(crate) fn nice_time_interval(wanted_interval: f64) -> Option<f64> {
+    if !(TIME_TICKS[0]..=TIME_TICKS[TIME_TICKS.len() - 1]).contains(&wanted_interval) {
+        return None;
+    }
+    TIME_TICKS.into_iter().min_by(|a, b| {
+        let a_distance = (a / wanted_interval).ln().abs();
+        let b_distance = (b / wanted_interval).ln().abs();
+        a_distance.total_cmp(&b_distance)
+    })
+}
+
+///the seconds since the unix epoch (negative before it)
+pub(crate) fn seconds_since_epoch(time: SystemTime) -> f64 {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(duration) => duration.as_secs_f64(),
+        Err(error) => -error.duration().as_secs_f64(),
+    }
+}
+
+///formats the time of the canvas value as utc
+///with the date at midnight and the time of day otherwise
+///fractions of a second are shown down to the precision of the f32 value (at most microseconds)
+pub(crate) fn format_time(value: f32, base: f64, unit: Duration) -> String {
+    const MAX_DECIMALS: u32 = 6;
+
+    let seconds_per_unit = unit.as_secs_f64();
+    //the digits below the resolution of the value are noise
+    let resolution = (value.abs() * f32::EPSILON) as f64 * seconds_per_unit;
+    let decimals = if resolution > 0.0 {
+        (-resolution.log10())
+            .floor()
+            .clamp(0.0, MAX_DECIMALS as f64) as u32
+    } else {
+        MAX_DECIMALS
+    };
+    let steps_per_second = 10_i64.pow(decimals);
+    let steps = ((base + value as f64 * seconds_per_unit) * steps_per_second as f64).round() as i64;
+    let seconds = steps.div_euclid(steps_per_second);
+    let fraction = steps.rem_euclid(steps_per_second);
+
+    let days = seconds.div_euclid(DAY as i64);
+    let second_of_day = seconds.rem_euclid(DAY as i64);
+
+    let hour = second_of_day / 3600;
+    let minute = second_of_day % 3600 / 60;
+    let second = second_of_day % 60;
+
+    if fraction != 0 {
+        let fraction = format!("{fraction:0width$}", width = decimals as usize);
+        let fraction = fraction.trim_end_matches('0');
+        format!("{hour:02}:{minute:02}:{second:02}.{fraction}")
+    } else if second_of_day == 0 {
+        let (year, month, day) = civil_from_days(days);
+        format!("{year:04}-{month:02}-{day:02}")
+    } else if second == 0 {
+        format!("{hour:02}:{minute:02}")
+    } else {
+        format!("{hour:02}:{minute:02}:{second:02}")
+    }
+}
+
+///converts the days since the unix epoch to (year, month, day)
+///see http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    ///2022-04-15 05:20:00 utc
+    const BASE: f64 = 1_650_000_000.0;
+
+    #[test]
+    fn sub_second_ticks_get_distinct_labels() {
+        let labels: Vec<String> = (0..5)
+            .map(|index| format_time(index as f32 * 0.2, BASE, Duration::from_secs(1)))
+            .collect();
+        assert_eq!(
+            labels,
+            [
+                "05:20",
+                "05:20:00.2",
+                "05:20:00.4",
+                "05:20:00.6",
+                "05:20:00.8"
+            ]
+        );
+    }
+
+    #[test]
+    fn milliseconds_far_from_the_base_are_not_noisy() {
+        //3600.1 is 3600.10009765625 as f32
+        let label = format_time(3600.1, BASE, Duration::from_secs(1));
+        assert_eq!(label, "06:20:00.1");
+    }
+
+    #[test]
+    fn midnight_is_formatted_as_the_date() {
+        let midnight = (BASE / DAY).ceil() * DAY;
+        let value = (midnight - BASE) as f32;
+        assert_eq!(
+            format_time(value, BASE, Duration::from_secs(1)),
+            "2022-04-16"
+        );
+    }
+
+    #[test]
+    fn nice_time_intervals_include_fractions_of_a_second() {
+        assert_eq!(nice_time_interval(0.0011), Some(0.001));
+        assert_eq!(nice_time_interval(0.18), Some(0.2));
+        assert_eq!(nice_time_interval(0.0001), None);
+    }
+}
@@ -0,0 +1,238 @@
+use std::ops::Range;
+
+use eframe::emath::Rect;
+
+use crate::{CanvasHandle, Drawable, Position, Response, Transform};
+
+///draws the drawable with its canvas positions transformed by the transform
+///the positions of the input (also curser_pos) are mapped back into the canvas space of the drawable
+///the handle of handle_input does not apply the transform
+///so use the positions of the response and do not convert gui positions with the handle
+///the input is forwarded unchanged if the transform is not invertible
+#[derive(Debug, Clone)]
+pub struct Transformed<T> {
+    drawable: T,
+    transform: Transform,
+}
+
+impl<T> Transformed<T> {
+    pub fn new(drawable: T, transform: Transform) -> Transformed<T> {
+        Transformed {
+            drawable,
+            transform,
+        }
+    }
+
+    pub fn set_transform(&mut self, transform: Transform) {
+        self.transform = transform;
+    }
+
+    pub fn inner(&self) -> &T {
+        &self.drawable
+    }
+
+    pub fn inner_mut(&mut self) -> &mut T {
+        &mut self.drawable
+    }
+
+    pub fn into_inner(self) -> T {
+        self.drawable
+    }
+}
+
+impl<T, D> Drawable for Transformed<T>
+where
+    T: Drawable<DrawData = D>,
+{
+    type DrawData = D;
+
+    fn draw(&mut self, handle: &mut CanvasHandle, draw_data: &Self::DrawData) {
+        handle.push_transform(self.transform);
+        self.drawable.draw(handle, draw_data);
+        handle.pop_transform();
+    }
+
+    fn draw_range(
+        &mut self,
+        handle: &mut CanvasHandle,
+        draw_data: &Self::DrawData,
+        range: Range<f32>,
+    ) {
+        handle.push_transform(self.transform);
+        self.drawable.draw_range(handle, draw_data, range);
+        handle.pop_transform();
+    }
+
+    fn draw_overlay(&mut self, handle: &mut CanvasHandle, draw_data: &Self::DrawData) {
+        handle.push_transform(self.transform);
+        self.drawable.draw_overlay(handle, draw_data);
+        handle.pop_transform();
+    }
+
+    fn get_cutout(&mut self, draw_data: &Self::DrawData) -> Rect {
+        let cutout = self.drawable.get_cutout(draw_data);
+        if cutout.is_negative() {
+            //no content
+            cutout
+        } else {
+            self.transform.apply_to_rect(cutout)
+        }
+    }
+
+    fn handle_input(&mut self, response: &Response, handle: &CanvasHandle) {
+        let inverse = match self.transform.inverse() {
+            Some(inverse) => inverse,
+            None => return self.drawable.handle_input(response, handle),
+        };
+        //gui and overlay positions become canvas positions
+        //because the handle would convert them into the untransformed space
+        let to_drawable_space = |pos: Option<Position>| {
+            pos.map(|pos| {
+                let canvas_pos = handle.convert_to_canvas_space(pos).get_raw_pos();
+                Position::Canvas(inverse.apply(canvas_pos))
+            })
+        };
+        let response = Response {
+            curser_pos: to_drawable_space(response.curser_pos),
+            clicked: response.clicked,
+            canvas_pos: to_drawable_space(response.canvas_pos),
+            dragged: response.dragged,
+            drag_delta_canvas: inverse.apply_to_vec(response.drag_delta_canvas),
+            scroll_delta: response.scroll_delta,
+            selection: response
+                .selection
+                .map(|selection| inverse.apply_to_rect(selection)),
+            selection_committed: response.selection_committed,
+            secondary_clicked: response.secondary_clicked,
+            secondary_click_pos: to_drawable_space(response.secondary_click_pos),
+        };
+        self.drawable.handle_input(&response, handle);
+    }
+
+    fn z_order(&self) -> i32 {
+        self.drawable.z_order()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use eframe::emath::{pos2, Pos2};
+    use simple_math::Vec2;
+
+    use super::*;
+    use crate::{PickRadiusMode, RecordingPainter, RegionInput};
+
+    ///remembers the canvas fields of the last input
+    #[derive(Default)]
+    struct LastInput {
+        canvas_pos: Option<Pos2>,
+        drag_delta: Option<Vec2>,
+        selection: Option<Rect>,
+    }
+
+    impl Drawable for LastInput {
+        type DrawData = ();
+
+        fn draw(&mut self, _handle: &mut CanvasHandle, _draw_data: &()) {}
+
+        fn get_cutout(&mut self, _draw_data: &()) -> Rect {
+            Rect::NOTHING
+        }
+
+        fn handle_input(&mut self, response: &Response, _handle: &CanvasHandle) {
+            self.canvas_pos = response.canvas_pos.map(Position::get_raw_pos);
+            self.drag_delta = Some(response.drag_delta_canvas);
+            self.selection = response.selection;
+        }
+    }
+
+    fn handle<'p>(painter: &'p mut RecordingPainter, cutout: &'p mut Rect) -> CanvasHandle<'p> {
+        let gui_space = Rect::from_min_max(pos2(0.0, 0.0), pos2(100.0, 100.0));
+        CanvasHandle::new(
+            painter,
+            cutout,
+            gui_space,
+            1.0,
+            Default::default(),
+            PickRadiusMode::default(),
+        )
+    }
+
+    fn click_at_gui_center() -> Response {
+        //the center of the gui space is at (5.0, 5.0) in the canvas space
+        Response {
+            curser_pos: Some(Position::Gui(pos2(50.0, 50.0))),
+            clicked: true,
+            canvas_pos: Some(Position::Canvas(pos2(5.0, 5.0))),
+            dragged: false,
+            drag_delta_canvas: Vec2::new(0.0, 0.0),
+            scroll_delta: 0.0,
+            selection: None,
+            selection_committed: false,
+            secondary_clicked: false,
+            secondary_click_pos: None,
+        }
+    }
+
+    #[test]
+    fn input_is_mapped_into_the_space_of_the_drawable() {
+        let mut painter = RecordingPainter::new(Rect::EVERYTHING);
+        let mut cutout = Rect::from_min_max(pos2(0.0, 0.0), pos2(10.0, 10.0));
+        let handle = handle(&mut painter, &mut cutout);
+
+        let transform =
+            Transform::scale(Vec2::new(2.0, 2.0)).then(Transform::translation(Vec2::new(1.0, 0.0)));
+        let mut transformed = Transformed::new(LastInput::default(), transform);
+        let response = Response {
+            curser_pos: None,
+            clicked: true,
+            canvas_pos: Some(Position::Canvas(pos2(5.0, 4.0))),
+            dragged: true,
+            drag_delta_canvas: Vec2::new(2.0, -2.0),
+            scroll_delta: 0.0,
+            selection: Some(Rect::from_min_max(pos2(1.0, 0.0), pos2(3.0, 2.0))),
+            selection_committed: false,
+            secondary_clicked: false,
+            secondary_click_pos: None,
+        };
+        transformed.handle_input(&response, &handle);
+
+        let last_input = transformed.inner();
+        assert_eq!(last_input.canvas_pos, Some(pos2(2.0, 2.0)));
+        let drag_delta = last_input.drag_delta.unwrap();
+        assert_eq!((drag_delta.x(), drag_delta.y()), (1.0, -1.0));
+        assert_eq!(
+            last_input.selection,
+            Some(Rect::from_min_max(pos2(0.0, 0.0), pos2(1.0, 1.0)))
+        );
+    }
+
+    #[test]
+    fn wrapped_region_input_tests_the_cursor_in_the_space_of_the_drawable() {
+        let mut painter = RecordingPainter::new(Rect::EVERYTHING);
+        let mut cutout = Rect::from_min_max(pos2(0.0, 0.0), pos2(10.0, 10.0));
+        let handle = handle(&mut painter, &mut cutout);
+
+        //the cursor is at (-5.0, 5.0) in the space of the drawable
+        let transform = Transform::translation(Vec2::new(10.0, 0.0));
+        let around_cursor = Rect::from_min_max(pos2(-6.0, 4.0), pos2(-4.0, 6.0));
+        let mut transformed = Transformed::new(
+            RegionInput::new(LastInput::default(), around_cursor),
+            transform,
+        );
+        transformed.handle_input(&click_at_gui_center(), &handle);
+        assert_eq!(
+            transformed.inner().inner().canvas_pos,
+            Some(pos2(-5.0, 5.0))
+        );
+
+        //the untransformed cursor position is not in the space of the drawable
+        let around_untransformed = Rect::from_min_max(pos2(4.0, 4.0), pos2(6.0, 6.0));
+        let mut transformed = Transformed::new(
+            RegionInput::new(LastInput::default(), around_untransformed),
+            transform,
+        );
+        transformed.handle_input(&click_at_gui_center(), &handle);
+        assert_eq!(transformed.inner().inner().canvas_pos, None);
+    }
+}